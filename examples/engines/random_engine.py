@@ -0,0 +1,31 @@
+#!/usr/bin/env python3
+# plays a random legal card , a starting point for engines in other languages
+import json
+import random
+import sys
+
+RANKS = {"Two": "2", "Three": "3", "Four": "4", "Five": "5", "Six": "6", "Seven": "7", "Eight": "8",
+         "Nine": "9", "Ten": "T", "Jack": "J", "Queen": "Q", "King": "K", "Ace": "A"}
+SUITS = {"Spade": "S", "Heart": "H", "Diamond": "D", "Club": "C"}
+
+
+def short(card):
+    return RANKS[card["rank"]] + SUITS[card["suit"]]
+
+
+def say(line):
+    print(line, flush=True)
+
+
+state = None
+for line in sys.stdin:
+    word, _, rest = line.strip().partition(" ")
+    if word == "hello":
+        say("hello random_engine")
+    elif word == "state":
+        state = json.loads(rest)
+    elif word == "go":
+        say("play " + short(random.choice(state["legal"])))
+    elif word == "quit":
+        break
+    # `event` lines are ignored , a real engine would keep track of the game with them
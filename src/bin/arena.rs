@@ -0,0 +1,290 @@
+// ranks bots against each other on duplicate deals , built in ones and engines alike
+//
+//   cargo run --release --bin arena -- --entrants greedy,counting,engine:random --engines engines.toml --deals 500 --report arena.md
+//
+// every pair of entrants plays every deal , sitting in turns round the table (A B A B) , once for every
+// rotation of the hands and again with the seats swapped , so both play every hand from every seat.
+// the side holding the loser scores 0 for the game and the other side 1 , a deal's score is the average.
+// all games of one deal share the same seed , so the bots are the only thing that changes.
+// an engine is started once and plays all its seats in every game , it gets the `state` for every move
+// but no table events since games run side by side
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use card_game::bot::{Bot, play_bots};
+use card_game::bot::engine::{EngineBot, Engines};
+use card_game::bot::profile::{BotProfile, Difficulty};
+use card_game::bot::rating::{elo, interval};
+use card_game::game::card::{shuffle, standard_deck, Card};
+use card_game::game::event::Event;
+use card_game::game::logic::Game;
+use card_game::game::players::PlayerId;
+use card_game::game::rng;
+use card_game::game::rules::HouseRules;
+use card_game::game::settings::{RoomSettings, SearchBudget, MAX_PLAYERS, MIN_PLAYERS};
+
+#[derive(Clone)]
+enum Entrant {
+    Builtin(Difficulty),
+    Engine(String),
+}
+
+struct Options {
+    entrants : Vec<(String, Entrant)>,
+    deals : u64,
+    seed : u64,
+    players : usize,
+    threads : usize,
+    search : SearchBudget,
+    rules : HouseRules,
+    engines : Engines,
+    report : Option<String>,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: arena --entrants BOT,BOT,... [--deals N] [--seed S] [--players P] [--threads T] [--search-iterations N] [--rules FILE] [--engines FILE] [--report FILE]");
+    eprintln!("bots are random , greedy , counting , search or engine:NAME for an engine listed in --engines (default BOT_ENGINES)");
+    std::process::exit(2);
+}
+
+fn entrant(name : &str, engines : &Engines) -> Option<Entrant> {
+    if let Some(engine) = name.strip_prefix("engine:") {
+        return engines.get(engine).map(|_| Entrant::Engine(engine.to_string()));
+    }
+    name.parse().ok().map(Entrant::Builtin)
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        entrants : Vec::new(),
+        deals : 200,
+        seed : 0,
+        players : MAX_PLAYERS,
+        threads : std::thread::available_parallelism().map_or(1, |n| n.get()),
+        search : SearchBudget::Iterations(200),
+        rules : HouseRules::default(),
+        engines : Engines::default(),
+        report : None,
+    };
+    let mut names = String::new();
+    let mut engines = std::env::var("BOT_ENGINES").ok();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            usage();
+        };
+        match flag.as_str() {
+            "--entrants" => names = value,
+            "--deals" => options.deals = value.parse().unwrap_or_else(|_| usage()),
+            "--seed" => options.seed = value.parse().unwrap_or_else(|_| usage()),
+            "--players" => options.players = value.parse().unwrap_or_else(|_| usage()),
+            "--threads" => options.threads = value.parse::<usize>().unwrap_or_else(|_| usage()).max(1),
+            "--search-iterations" => options.search = SearchBudget::Iterations(value.parse().unwrap_or_else(|_| usage())),
+            "--rules" => options.rules = HouseRules::load(value.as_ref()).unwrap_or_else(|e| {
+                eprintln!("bad house rules {e}");
+                std::process::exit(2);
+            }),
+            "--engines" => engines = Some(value),
+            "--report" => options.report = Some(value),
+            _ => usage(),
+        }
+    }
+
+    if let Some(path) = engines {
+        options.engines = Engines::load(path.as_ref()).unwrap_or_else(|e| {
+            eprintln!("bad engine list {e}");
+            std::process::exit(2);
+        });
+    }
+    for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let Some(e) = entrant(name, &options.engines) else {
+            eprintln!("unknown entrant {name}");
+            usage();
+        };
+        options.entrants.push((name.to_string(), e));
+    }
+    if options.entrants.len() < 2 || !(MIN_PLAYERS..=MAX_PLAYERS).contains(&options.players) {
+        usage();
+    }
+    options
+}
+
+/// the one running engine of an entrant , lent to every seat it plays
+#[derive(Clone)]
+struct SharedEngine(Arc<Mutex<EngineBot>>);
+
+impl Bot for SharedEngine {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        self.0.lock().expect("no worker panicked").choose_card(game, me)
+    }
+
+    fn choose_pass(&mut self, game : &Game, me : PlayerId) -> Vec<Card> {
+        self.0.lock().expect("no worker panicked").choose_pass(game, me)
+    }
+
+    fn choose_bid(&mut self, game : &Game, me : PlayerId) -> u32 {
+        self.0.lock().expect("no worker panicked").choose_bid(game, me)
+    }
+}
+
+/// starts every engine entrant once , None for the built in ones
+fn start_engines(options : &Options) -> Vec<Option<SharedEngine>> {
+    options.entrants.iter().map(|(_, entrant)| match entrant {
+        Entrant::Builtin(_) => None,
+        Entrant::Engine(name) => {
+            let config = options.engines.get(name).expect("entrants are checked against the engine list");
+            let fallback = BotProfile { difficulty : Difficulty::Greedy, ..BotProfile::default() }.make(options.search);
+            Some(SharedEngine(Arc::new(Mutex::new(EngineBot::new(name, config, fallback)))))
+        }
+    }).collect()
+}
+
+fn make_bot(options : &Options, engines : &[Option<SharedEngine>], entrant : usize) -> Box<dyn Bot> {
+    match (&options.entrants[entrant].1, &engines[entrant]) {
+        (_, Some(engine)) => Box::new(engine.clone()),
+        (Entrant::Builtin(difficulty), None) => BotProfile { difficulty : *difficulty, ..BotProfile::default() }.make(options.search),
+        (Entrant::Engine(_), None) => unreachable!("engines are started up front"),
+    }
+}
+
+/// one game between the entrants `a` and `b` , `a_seats[i]` says whether seat i is played by `a`. the score is `a`'s ,
+/// a game still going after `MAX_PLAYS` cards is a draw
+fn play(options : &Options, engines : &[Option<SharedEngine>], deck : &[Card], seed : u64, rotation : usize, (a, b) : (usize, usize), a_seats : &[bool]) -> f64 {
+    rng::seed(Some(seed));
+
+    let settings = RoomSettings { max_players : options.players, rules : options.rules.clone(), bot_search : options.search, ..RoomSettings::default() };
+    let mut game = Game::with_settings(settings);
+    for _ in 0..options.players {
+        game.add_bot(BotProfile::default());
+    }
+    game.set_fixed_deal(deck.to_vec(), rotation);
+    game.start_game();
+
+    let seats = game.get_seats();
+    let mut bots : Vec<(_, Box<dyn Bot>)> = seats.iter().zip(a_seats).map(|(&p, &is_a)| (p, make_bot(options, engines, if is_a { a } else { b }))).collect();
+
+    let mut loser = None;
+    play_bots(&mut game, &mut bots, &mut 0, |_| false, |ev| if let Event::EndGame { p_id } = *ev {
+        loser = seats.iter().position(|&p| p == p_id);
+    });
+    match loser {
+        Some(seat) if a_seats[seat] => 0.0,
+        Some(_) => 1.0,
+        None => 0.5,
+    }
+}
+
+fn main() {
+    let options = parse_args();
+    let started = Instant::now();
+    let n = options.entrants.len();
+    let engines = start_engines(&options);
+    let pairs : Vec<(usize, usize)> = (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b))).collect();
+
+    let decks : Vec<(u64, Vec<Card>)> = (0..options.deals).map(|d| {
+        let seed = options.seed.wrapping_mul(1_000_003).wrapping_add(d);
+        rng::seed(Some(seed));
+        let mut deck = standard_deck();
+        shuffle(&mut deck);
+        (seed, deck)
+    }).collect();
+
+    // both seatings for every rotation
+    let layouts : Vec<(usize, Vec<bool>)> = (0..options.players).flat_map(|rotation| {
+        [true, false].map(|first| (rotation, (0..options.players).map(|seat| (seat % 2 == 0) == first).collect()))
+    }).collect();
+
+    // every game is a job , results land in their own slot so the totals don't depend on the threads
+    let jobs = pairs.len() * decks.len() * layouts.len();
+    let results = Mutex::new(vec![0.5; jobs]);
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| loop {
+                let job = next.fetch_add(1, Ordering::Relaxed);
+                if job >= jobs {
+                    break;
+                }
+                let (pair, rest) = (job / (decks.len() * layouts.len()), job % (decks.len() * layouts.len()));
+                let (deal, layout) = (rest / layouts.len(), rest % layouts.len());
+                let (seed, deck) = &decks[deal];
+                let (rotation, a_seats) = &layouts[layout];
+
+                let score = play(&options, &engines, deck, *seed, *rotation, pairs[pair], a_seats);
+                results.lock().expect("no worker panicked")[job] = score;
+            });
+        }
+    });
+    let results = results.into_inner().expect("no worker panicked");
+
+    // one sample per deal : the average over all its games
+    let mut against : Vec<Vec<Vec<f64>>> = vec![vec![Vec::new(); n]; n];
+    for (pair, &(a, b)) in pairs.iter().enumerate() {
+        for deal in 0..decks.len() {
+            let start = (pair * decks.len() + deal) * layouts.len();
+            let score = results[start..start + layouts.len()].iter().sum::<f64>() / layouts.len() as f64;
+            against[a][b].push(score);
+            against[b][a].push(1.0 - score);
+        }
+    }
+
+    let report = write_report(&options, &against, jobs, started);
+    print!("{report}");
+    let written = options.report.as_ref().map_or(Ok(()), |path| std::fs::write(path, &report).map_err(|e| format!("{path}: {e}")));
+    if let Err(e) = written {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+fn write_report(options : &Options, against : &[Vec<Vec<f64>>], games : usize, started : Instant) -> String {
+    let names : Vec<&str> = options.entrants.iter().map(|(name, _)| name.as_str()).collect();
+    let mut out = String::new();
+    let mut line = |text : String| {
+        out.push_str(&text);
+        out.push('\n');
+    };
+
+    line("# Arena".into());
+    line(String::new());
+    line(format!("{} entrants , {} deals , {} players a table , seed {} , {games} games in {:.1?}", names.len(), options.deals, options.players, options.seed, started.elapsed()));
+    line(String::new());
+
+    // rating against the whole field , the interval comes from the spread of deal scores
+    let mut ratings : Vec<(usize, f64, f64)> = (0..names.len()).map(|i| {
+        let samples : Vec<f64> = against[i].iter().flatten().copied().collect();
+        let (score, half) = interval(&samples);
+        (i, score, half)
+    }).collect();
+    ratings.sort_by(|x, y| y.1.total_cmp(&x.1));
+
+    line("## Ratings".into());
+    line(String::new());
+    line("| rank | entrant | score | elo | 95% interval |".into());
+    line("| --- | --- | --- | --- | --- |".into());
+    for (rank, (i, score, half)) in ratings.iter().enumerate() {
+        line(format!("| {} | {} | {:.3} ± {:.3} | {:+.0} | {:+.0} to {:+.0} |", rank + 1, names[*i], score, half, elo(*score), elo(score - half), elo(score + half)));
+    }
+    line(String::new());
+
+    line("## Head to head".into());
+    line(String::new());
+    line("score of the row against the column over its deals , * when the interval leaves out 0.5".into());
+    line(String::new());
+    line(format!("| | {} |", names.join(" | ")));
+    line(format!("| --- |{}", " --- |".repeat(names.len())));
+    for (i, row) in against.iter().enumerate() {
+        let cells : Vec<String> = row.iter().enumerate().map(|(j, samples)| {
+            if i == j {
+                return "-".into();
+            }
+            let (score, half) = interval(samples);
+            let sure = if (score - 0.5).abs() > half { "*" } else { "" };
+            format!("{score:.3} ± {half:.3}{sure}")
+        }).collect();
+        line(format!("| {} | {} |", names[i], cells.join(" | ")));
+    }
+    out
+}
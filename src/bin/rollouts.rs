@@ -0,0 +1,124 @@
+// plays classic episodes in the training environment and writes them out for learning
+//
+//   cargo run --release --bin rollouts -- --episodes 10000 --policy counting --format npy --out data
+//
+// the agent sits in the first seat and is played by `--policy` , every other seat by `--opponents`
+// episode n is seeded from `--seed` and n , so the same command writes the same data
+
+use std::path::PathBuf;
+use card_game::bot::profile::{BotProfile, Difficulty};
+use card_game::env::{card_index, Env, EnvConfig};
+use card_game::env::export::{rollout, write_jsonl, write_npy};
+use card_game::game::rng;
+use card_game::game::rules::HouseRules;
+use card_game::game::settings::{SearchBudget, MAX_PLAYERS, MIN_PLAYERS};
+
+enum Format {
+    Npy,
+    Jsonl,
+}
+
+struct Options {
+    episodes : u64,
+    seed : u64,
+    players : usize,
+    policy : Difficulty,
+    opponents : Difficulty,
+    search : SearchBudget,
+    rules : HouseRules,
+    format : Format,
+    out : PathBuf,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: rollouts [--episodes N] [--seed S] [--players P] [--policy BOT] [--opponents BOT] [--search-iterations N] [--rules FILE] [--format npy|jsonl] [--out PATH]");
+    eprintln!("bots are random , greedy , counting or search , {MIN_PLAYERS} to {MAX_PLAYERS} players");
+    eprintln!("npy writes a directory of arrays , jsonl writes one transition per line");
+    std::process::exit(2);
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        episodes : 1000,
+        seed : 0,
+        players : MAX_PLAYERS,
+        policy : Difficulty::Random,
+        opponents : Difficulty::Greedy,
+        search : SearchBudget::Iterations(100),
+        rules : HouseRules::default(),
+        format : Format::Npy,
+        out : PathBuf::from("rollouts"),
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            usage();
+        };
+        match flag.as_str() {
+            "--episodes" => options.episodes = value.parse().unwrap_or_else(|_| usage()),
+            "--seed" => options.seed = value.parse().unwrap_or_else(|_| usage()),
+            "--players" => options.players = value.parse().unwrap_or_else(|_| usage()),
+            "--policy" => options.policy = value.parse().unwrap_or_else(|_| usage()),
+            "--opponents" => options.opponents = value.parse().unwrap_or_else(|_| usage()),
+            "--search-iterations" => options.search = SearchBudget::Iterations(value.parse().unwrap_or_else(|_| usage())),
+            "--rules" => options.rules = HouseRules::load(value.as_ref()).unwrap_or_else(|e| {
+                eprintln!("bad house rules {e}");
+                std::process::exit(2);
+            }),
+            "--format" => options.format = match value.as_str() {
+                "npy" => Format::Npy,
+                "jsonl" => Format::Jsonl,
+                _ => usage(),
+            },
+            "--out" => options.out = PathBuf::from(value),
+            _ => usage(),
+        }
+    }
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&options.players) {
+        usage();
+    }
+    options
+}
+
+fn main() {
+    let options = parse_args();
+    let config = EnvConfig {
+        players : options.players,
+        opponents : BotProfile { difficulty : options.opponents, ..BotProfile::default() },
+        search : options.search,
+        rules : options.rules.clone(),
+    };
+    let mut env = Env::new(config);
+    // the env keeps its own rng , the thread's is left for the agent
+    rng::seed(Some(options.seed));
+    let mut agent = BotProfile { difficulty : options.policy, ..BotProfile::default() }.make(options.search);
+
+    let mut transitions = Vec::new();
+    let mut returns = 0.0;
+    for n in 0..options.episodes {
+        let seed = options.seed.wrapping_mul(1_000_003).wrapping_add(n);
+        let episode = rollout(&mut env, n, seed, &mut |env, _, mask| {
+            let game = env.get_game();
+            agent.choose_card(game, env.get_agent()).map(|c| card_index(&c))
+                .unwrap_or_else(|| mask.iter().position(|&legal| legal).unwrap_or(0))
+        });
+        let episode = episode.unwrap_or_else(|e| {
+            eprintln!("episode {n} : {e}");
+            std::process::exit(1);
+        });
+        returns += episode.iter().map(|t| t.reward as f64).sum::<f64>();
+        transitions.extend(episode);
+    }
+
+    let written = match options.format {
+        Format::Npy => write_npy(&options.out, &transitions),
+        Format::Jsonl => write_jsonl(&options.out, &transitions),
+    };
+    if let Err(e) = written {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    println!("{} episodes , {} transitions , average return {:.3} , written to {}",
+        options.episodes, transitions.len(), returns / options.episodes.max(1) as f64, options.out.display());
+}
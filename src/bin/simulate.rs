@@ -0,0 +1,177 @@
+// runs whole classic games between bots with no server , to tune rules and bots
+//
+//   cargo run --release --bin simulate -- --games 10000 --bots counting,greedy,greedy,greedy --seed 7
+//
+// every game is seeded from `--seed` and its number , so the same command prints the same numbers
+// however many threads run it
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use card_game::bot::{Bot, MAX_PLAYS, play_bots};
+use card_game::bot::profile::{BotProfile, Difficulty};
+use card_game::game::card::{Card, Rank, Suit};
+use card_game::game::event::Event;
+use card_game::game::logic::{Game, GamePhase};
+use card_game::game::rng;
+use card_game::game::rules::HouseRules;
+use card_game::game::settings::{RoomSettings, SearchBudget, MAX_PLAYERS, MIN_PLAYERS};
+
+const ACE_OF_SPADES : Card = Card { rank : Rank::Ace, suit : Suit::Spade };
+
+struct Options {
+    games : u64,
+    seed : u64,
+    threads : usize,
+    bots : Vec<Difficulty>,
+    search : SearchBudget,
+    rules : HouseRules,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: simulate [--games N] [--seed S] [--threads T] [--bots greedy,counting,...] [--search-iterations N] [--rules FILE]");
+    eprintln!("bots are random , greedy , counting or search , one per seat , {MIN_PLAYERS} to {MAX_PLAYERS} seats");
+    std::process::exit(2);
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        games : 1000,
+        seed : 0,
+        threads : std::thread::available_parallelism().map_or(1, |n| n.get()),
+        bots : vec![Difficulty::Greedy; MAX_PLAYERS],
+        search : SearchBudget::Iterations(200),
+        rules : HouseRules::default(),
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            usage();
+        };
+        match flag.as_str() {
+            "--games" => options.games = value.parse().unwrap_or_else(|_| usage()),
+            "--seed" => options.seed = value.parse().unwrap_or_else(|_| usage()),
+            "--threads" => options.threads = value.parse::<usize>().unwrap_or_else(|_| usage()).max(1),
+            "--bots" => options.bots = value.split(',').map(|b| b.parse().unwrap_or_else(|_| usage())).collect(),
+            "--search-iterations" => options.search = SearchBudget::Iterations(value.parse().unwrap_or_else(|_| usage())),
+            "--rules" => options.rules = HouseRules::load(value.as_ref()).unwrap_or_else(|e| {
+                eprintln!("bad house rules {e}");
+                std::process::exit(2);
+            }),
+            _ => usage(),
+        }
+    }
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&options.bots.len()) {
+        usage();
+    }
+    options
+}
+
+/// totals over any number of games , in seat order where it is per seat
+#[derive(Default)]
+struct Totals {
+    games : u64,
+    unfinished : u64,
+    losses : Vec<u64>,
+    /// games the player dealt A♠ lost
+    ace_holder_lost : u64,
+    plays : u64,
+    tricks : u64,
+    fouls : u64,
+}
+
+impl Totals {
+    fn add(&mut self, other : &Totals) {
+        self.games += other.games;
+        self.unfinished += other.unfinished;
+        self.losses.resize(self.losses.len().max(other.losses.len()), 0);
+        for (total, lost) in self.losses.iter_mut().zip(&other.losses) {
+            *total += lost;
+        }
+        self.ace_holder_lost += other.ace_holder_lost;
+        self.plays += other.plays;
+        self.tricks += other.tricks;
+        self.fouls += other.fouls;
+    }
+}
+
+/// plays game number `n` to the end
+fn play(options : &Options, n : u64) -> Totals {
+    rng::seed(Some(options.seed.wrapping_mul(1_000_003).wrapping_add(n)));
+
+    let settings = RoomSettings { max_players : options.bots.len(), rules : options.rules.clone(), bot_search : options.search, ..RoomSettings::default() };
+    let mut game = Game::with_settings(settings);
+    for &difficulty in &options.bots {
+        game.add_bot(BotProfile { difficulty, ..BotProfile::default() });
+    }
+    game.start_game();
+
+    let seats = game.get_seats();
+    let mut bots : Vec<(_, Box<dyn Bot>)> = seats.iter().map(|&p| (p, game.get_bot(p).unwrap_or_default().make(options.search))).collect();
+    let ace_holder = seats.iter().position(|&p| game.get_hand(p).is_some_and(|h| h.contains(&ACE_OF_SPADES)));
+
+    let mut totals = Totals { games : 1, losses : vec![0; seats.len()], ..Totals::default() };
+    play_bots(&mut game, &mut bots, &mut 0, |_| false, |ev| match *ev {
+        Event::CardPlayed { .. } => totals.plays += 1,
+        Event::DiscardPile => totals.tricks += 1,
+        Event::FoulGiven { .. } => totals.fouls += 1,
+        Event::EndGame { p_id } => {
+            let loser = seats.iter().position(|&p| p == p_id).expect("loser is seated");
+            totals.losses[loser] += 1;
+            if ace_holder == Some(loser) {
+                totals.ace_holder_lost += 1;
+            }
+        }
+        _ => {}
+    });
+    // out of cards to play or past the limit
+    if game.get_phase() == GamePhase::Playing {
+        totals.unfinished += 1;
+    }
+    totals
+}
+
+fn percent(part : u64, whole : u64) -> f64 {
+    if whole == 0 { 0.0 } else { 100.0 * part as f64 / whole as f64 }
+}
+
+fn main() {
+    let options = parse_args();
+    let started = Instant::now();
+
+    // workers take the next game number until all are played , the totals don't depend on who played what
+    let next = AtomicU64::new(0);
+    let totals = Mutex::new(Totals::default());
+    std::thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| {
+                let mut mine = Totals::default();
+                loop {
+                    let n = next.fetch_add(1, Ordering::Relaxed);
+                    if n >= options.games {
+                        break;
+                    }
+                    mine.add(&play(&options, n));
+                }
+                totals.lock().expect("no worker panicked").add(&mine);
+            });
+        }
+    });
+    let totals = totals.into_inner().expect("no worker panicked");
+
+    let finished = totals.games - totals.unfinished;
+    println!("{} games , seed {} , {} threads , {:.1?}", totals.games, options.seed, options.threads, started.elapsed());
+    println!();
+    println!("seat  bot        lost     rate");
+    for (seat, (bot, lost)) in options.bots.iter().zip(&totals.losses).enumerate() {
+        println!("{seat:<5} {:<10} {lost:<8} {:.1}%", format!("{bot:?}").to_lowercase(), percent(*lost, finished));
+    }
+    println!();
+    println!("A♠ holder lost     {} ({:.1}%)", totals.ace_holder_lost, percent(totals.ace_holder_lost, finished));
+    println!("game length        {:.1} cards , {:.1} tricks on average", totals.plays as f64 / totals.games.max(1) as f64, totals.tricks as f64 / totals.games.max(1) as f64);
+    println!("fouls              {:.2} per game , {:.2} per 100 cards", totals.fouls as f64 / totals.games.max(1) as f64, percent(totals.fouls, totals.plays));
+    if totals.unfinished > 0 {
+        println!("unfinished         {} (given up after {MAX_PLAYS} cards)", totals.unfinished);
+    }
+}
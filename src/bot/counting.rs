@@ -0,0 +1,76 @@
+use crate::bot::{Bot, shortest_suit, still_to_play};
+use crate::bot::profile::Personality;
+use crate::bot::simple::SimpleBot;
+use crate::game::card::{Card, Suit, standard_deck};
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+use crate::game::settings::GameMode;
+
+/// keeps count of the cards played and who fouled on what , classic games only
+/// stays under the trick while someone after it could still foul , and dumps high cards when nobody can
+pub struct CountingBot {
+    personality : Personality,
+}
+
+impl CountingBot {
+    pub fn new(personality : Personality) -> Self {
+        Self { personality }
+    }
+
+    /// cards of `suit` the others may still hold
+    fn out_in_suit(game : &Game, hand : &[Card], suit : Suit) -> usize {
+        let knowledge = game.get_knowledge();
+        let table : Vec<Card> = game.get_trick().iter().map(|(c, _)| *c).collect();
+        standard_deck()
+            .iter()
+            .filter(|c| c.suit == suit && !hand.contains(c) && !table.contains(c) && !knowledge.is_gone(c))
+            .count()
+    }
+}
+
+impl Bot for CountingBot {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        if game.get_settings().mode != GameMode::Classic {
+            return SimpleBot::new(self.personality).choose_card(game, me);
+        }
+        let legal = game.legal_cards(me);
+        let hand = game.get_hand(me)?;
+        if legal.len() < 2 {
+            return legal.first().copied();
+        }
+
+        let rules = &game.get_settings().rules;
+        let value = |c : &Card| rules.rank_value(c.rank);
+        let knowledge = game.get_knowledge();
+        let others = still_to_play(game, me);
+
+        let Some((led, _)) = game.get_trick().first().copied() else {
+            // lead a suit nobody is known to be void in , whatever is led can come back as a foul
+            let safe : Vec<Card> = legal.iter().copied().filter(|c| !others.iter().any(|&p| knowledge.is_void(p, c.suit))).collect();
+            let choices = if safe.is_empty() { legal } else { safe };
+            let choices = if self.personality.keeps_voids() {
+                shortest_suit(&hand, &choices)
+            } else {
+                // the suit the others hold most of , they all have to follow it
+                let suit = choices.iter().map(|c| c.suit).max_by_key(|&s| Self::out_in_suit(game, &hand, s))?;
+                choices.into_iter().filter(|c| c.suit == suit).collect()
+            };
+            return choices.into_iter().min_by_key(value);
+        };
+
+        if !hand.iter().any(|c| c.suit == led.suit) {
+            let choices = if self.personality.keeps_voids() { shortest_suit(&hand, &legal) } else { legal };
+            return choices.into_iter().max_by_key(value);
+        }
+
+        // nobody left to foul , the trick is discarded and the highest card goes with it
+        if others.is_empty() || (self.personality.dumps_high() && !others.iter().any(|&p| knowledge.is_void(p, led.suit))) {
+            return legal.into_iter().max_by_key(value);
+        }
+
+        // stay under the highest card so far so a foul goes to someone else
+        let top = game.get_trick().iter().filter(|(c, _)| c.suit == led.suit).map(|(c, _)| value(c)).max()?;
+        let under = legal.iter().copied().filter(|c| value(c) < top).max_by_key(value);
+        under.or_else(|| legal.into_iter().min_by_key(value))
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use serde::Deserialize;
+use crate::bot::Bot;
+use crate::game::card::Card;
+use crate::game::event::Event;
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+
+/// version sent with `hello` , bumped whenever the protocol changes
+pub const PROTOCOL_VERSION : u32 = 1;
+const DEFAULT_TIMEOUT_MS : u64 = 1000;
+/// the ticker waits this long at most for one move , longer would stall the table
+const MAX_TIMEOUT_MS : u64 = 10_000;
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+/// how to start one engine
+#[derive(Debug, Clone, Deserialize)]
+pub struct EngineConfig {
+    pub command : PathBuf,
+    #[serde(default)]
+    pub args : Vec<String>,
+    /// time for `hello` and for every move
+    #[serde(default = "default_timeout")]
+    pub timeout_ms : u64,
+}
+
+/// the only executables the server will ever start , by the name hosts pick them with
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Engines {
+    #[serde(default)]
+    engines : HashMap<String, EngineConfig>,
+}
+
+impl Engines {
+    /// reads the list from a `.json` file , anything else is parsed as toml
+    pub fn load(path : &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let engines = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&text).map_err(|e| e.to_string())
+        };
+        let engines : Self = engines.map_err(|e| format!("{}: {e}", path.display()))?;
+        for (name, config) in &engines.engines {
+            if !(1..=MAX_TIMEOUT_MS).contains(&config.timeout_ms) {
+                return Err(format!("{}: engine {name} needs a timeout_ms between 1 and {MAX_TIMEOUT_MS}", path.display()));
+            }
+        }
+        Ok(engines)
+    }
+
+    pub fn get(&self, name : &str) -> Option<&EngineConfig> {
+        self.engines.get(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names : Vec<String> = self.engines.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// a running engine , its pipes are served by their own threads so a stuck engine can't hold up the table
+struct Process {
+    /// only None once dropped
+    child : Option<Child>,
+    input : mpsc::Sender<String>,
+    lines : mpsc::Receiver<String>,
+}
+
+impl Process {
+    fn start(config : &EngineConfig) -> Result<Self, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| format!("{}: {e}", config.command.display()))?;
+
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let (input, queued) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            for line in queued {
+                if writeln!(stdin, "{line}").and_then(|_| stdin.flush()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { child : Some(child), input, lines })
+    }
+
+    fn has_exited(&mut self) -> bool {
+        matches!(self.lines.try_recv(), Err(mpsc::TryRecvError::Disconnected)) || self.child.as_mut().is_some_and(|child| child.try_wait().ok().flatten().is_some())
+    }
+
+    fn send(&mut self, line : &str) -> Result<(), String> {
+        self.input.send(line.to_string()).map_err(|_| "engine stopped reading".to_string())
+    }
+
+    /// the rest of the first line starting with `word` , other lines are skipped
+    fn expect(&mut self, word : &str, timeout : Duration) -> Result<String, String> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            let line = self.lines.recv_timeout(left).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => format!("no `{word}` within {} ms", timeout.as_millis()),
+                mpsc::RecvTimeoutError::Disconnected => "engine exited".to_string(),
+            })?;
+            if let Some(rest) = line.strip_prefix(word) {
+                return Ok(rest.trim().to_string());
+            }
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        // waiting on it blocks , done on a thread of its own so dropping a seat never holds up the runtime
+        std::thread::spawn(move || {
+            // a moment to exit on its own before it is killed
+            std::thread::sleep(Duration::from_millis(50));
+            let _ = child.kill();
+            let _ = child.wait();
+        });
+    }
+}
+
+/// a seat played by an outside program over stdin and stdout , see ENGINE_PROTOCOL.md
+/// a slow , crashed or confused engine is covered by the built in `fallback`
+pub struct EngineBot {
+    name : String,
+    process : Option<Process>,
+    timeout : Duration,
+    fallback : Box<dyn Bot>,
+}
+
+impl EngineBot {
+    pub fn new(name : &str, config : &EngineConfig, fallback : Box<dyn Bot>) -> Self {
+        let timeout = Duration::from_millis(config.timeout_ms);
+        let process = Process::start(config).and_then(|mut process| {
+            process.send(&format!("hello {PROTOCOL_VERSION}"))?;
+            process.expect("hello", timeout)?;
+            Ok(process)
+        });
+        let process = process.map_err(|e| eprintln!("engine {name} failed to start , using the built in bot : {e}")).ok();
+        Self { name : name.to_string(), process, timeout, fallback }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// stops talking to the engine for good , the fallback plays the rest
+    fn give_up(&mut self, reason : &str) {
+        eprintln!("engine {} dropped , using the built in bot : {reason}", self.name);
+        self.process = None;
+    }
+
+    fn ask(&mut self, game : &Game, me : PlayerId) -> Result<Card, String> {
+        let view = game.view_for(me).ok_or("not seated")?;
+        let process = self.process.as_mut().ok_or("not running")?;
+        // late answers to an earlier move are thrown away
+        while process.lines.try_recv().is_ok() {}
+
+        process.send(&format!("state {}", serde_json::to_string(&view).map_err(|e| e.to_string())?))?;
+        process.send(&format!("go {}", self.timeout.as_millis()))?;
+        process.expect("play", self.timeout)?.parse()
+    }
+}
+
+impl Bot for EngineBot {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        let legal = game.legal_cards(me);
+        if self.process.is_none() || legal.is_empty() {
+            return self.fallback.choose_card(game, me);
+        }
+
+        match self.ask(game, me) {
+            Ok(card) if legal.contains(&card) => Some(card),
+            Ok(card) => {
+                eprintln!("engine {} played {card} which is not legal here", self.name);
+                self.fallback.choose_card(game, me)
+            }
+            Err(e) => {
+                // a timeout leaves the engine running , it may be quicker next time
+                if self.process.as_mut().is_some_and(Process::has_exited) {
+                    self.give_up(&e);
+                } else {
+                    eprintln!("engine {} : {e}", self.name);
+                }
+                self.fallback.choose_card(game, me)
+            }
+        }
+    }
+
+    fn choose_pass(&mut self, game : &Game, me : PlayerId) -> Vec<Card> {
+        self.fallback.choose_pass(game, me)
+    }
+
+    fn choose_bid(&mut self, game : &Game, me : PlayerId) -> u32 {
+        self.fallback.choose_bid(game, me)
+    }
+
+    fn observe(&mut self, ev : &Event) {
+        let Some(process) = self.process.as_mut() else {
+            return;
+        };
+        let sent = serde_json::to_string(ev).map_err(|e| e.to_string()).and_then(|json| process.send(&format!("event {json}")));
+        if let Err(e) = sent {
+            self.give_up(&e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::{Rank, Suit};
+    use crate::game::settings::RoomSettings;
+
+    const THREE : Card = Card { rank : Rank::Three, suit : Suit::Spade };
+    const KING : Card = Card { rank : Rank::King, suit : Suit::Spade };
+
+    /// stands in for the built in bot , always the three
+    struct Fallback;
+
+    impl Bot for Fallback {
+        fn choose_card(&mut self, _game : &Game, _me : PlayerId) -> Option<Card> {
+            Some(THREE)
+        }
+    }
+
+    /// an engine written as a shell script , it answers `hello` and then runs `on_go` for every `go`
+    fn engine(on_go : &str) -> EngineBot {
+        let script = format!("read l; echo 'hello test'; while read l; do case \"$l\" in go*) {on_go};; esac; done");
+        let config = EngineConfig { command : "sh".into(), args : vec!["-c".into(), script], timeout_ms : 200 };
+        EngineBot::new("test", &config, Box::new(Fallback))
+    }
+
+    /// seat 0 leads holding 3♠ and K♠ , both may be played
+    fn table() -> Game {
+        let mut game = Game::with_settings(RoomSettings { max_players : 2, ..RoomSettings::default() });
+        game.add_player();
+        game.add_player();
+        game.set_preset_hands(vec![vec![THREE, KING], vec![Card { rank : Rank::Four, suit : Suit::Spade }]], 0);
+        game.start_game();
+        game
+    }
+
+    #[test]
+    fn a_legal_answer_is_played() {
+        let mut bot = engine("echo 'play KS'");
+        assert_eq!(bot.choose_card(&table(), PlayerId(0)), Some(KING));
+    }
+
+    #[test]
+    fn bad_answers_fall_back_for_that_move() {
+        for answer in ["echo 'play 2H'", "echo 'play ZZ'", "sleep 1"] {
+            let mut bot = engine(answer);
+            assert_eq!(bot.choose_card(&table(), PlayerId(0)), Some(THREE), "{answer}");
+            // still running , it is asked again next time
+            assert!(bot.process.is_some(), "{answer}");
+        }
+    }
+
+    #[test]
+    fn an_engine_that_exits_is_dropped() {
+        let mut bot = engine("exit");
+        let game = table();
+        assert_eq!(bot.choose_card(&game, PlayerId(0)), Some(THREE));
+        assert!(bot.process.is_none());
+        assert_eq!(bot.choose_card(&game, PlayerId(0)), Some(THREE));
+    }
+
+    #[test]
+    fn no_hello_means_the_built_in_bot_plays() {
+        let silent = EngineConfig { command : "sh".into(), args : vec!["-c".into(), "sleep 1".into()], timeout_ms : 100 };
+        let mut bot = EngineBot::new("silent", &silent, Box::new(Fallback));
+        assert!(bot.process.is_none());
+        assert_eq!(bot.choose_card(&table(), PlayerId(0)), Some(THREE));
+
+        let missing = EngineConfig { command : "/no/such/engine".into(), args : vec![], timeout_ms : 100 };
+        assert!(EngineBot::new("missing", &missing, Box::new(Fallback)).process.is_none());
+    }
+}
@@ -0,0 +1,201 @@
+use serde::Serialize;
+use crate::bot::{Bot, still_to_play};
+use crate::bot::simple::SimpleBot;
+use crate::game::card::{Card, Rank, Suit};
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+use crate::game::settings::GameMode;
+
+/// a legal card with why it is a good or bad idea
+#[derive(Debug, Clone, Serialize)]
+pub struct Hint {
+    pub card : Card,
+    pub reason : String,
+}
+
+fn suit_symbol(suit : Suit) -> char {
+    match suit {
+        Suit::Spade => '♠',
+        Suit::Heart => '♥',
+        Suit::Diamond => '♦',
+        Suit::Club => '♣',
+    }
+}
+
+/// `A♦` , `10♣`
+fn card_name(card : &Card) -> String {
+    let rank = match card.rank {
+        Rank::Ace => "A".to_string(),
+        Rank::King => "K".to_string(),
+        Rank::Queen => "Q".to_string(),
+        Rank::Jack => "J".to_string(),
+        rank => (rank as u8).to_string(),
+    };
+    format!("{rank}{}", suit_symbol(card.suit))
+}
+
+/// the legal cards of `me` , best first , empty when there is nothing to play
+pub fn rank(game : &Game, me : PlayerId) -> Vec<Hint> {
+    let legal = game.legal_cards(me);
+    let Some(hand) = game.get_hand(me) else {
+        return vec![];
+    };
+    let mut scored : Vec<(i32, Hint)> = match game.get_settings().mode {
+        GameMode::Classic => legal.iter().map(|c| classic(game, me, &hand, c)).collect(),
+        GameMode::Hearts => legal.iter().map(|c| stay_low(game, &hand, c)).collect(),
+        // no reasoning of our own here , what the greedy bot would play comes first
+        GameMode::Callbreak | GameMode::CrazyEights => {
+            let pick = SimpleBot::default().choose_card(game, me);
+            legal.iter().map(|&card| {
+                if Some(card) == pick {
+                    (1, Hint { card, reason : "what the bots would play".into() })
+                } else {
+                    (0, Hint { card, reason : "also legal".into() })
+                }
+            }).collect()
+        }
+    };
+    // stable , so equal scores keep the order of the hand
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, hint)| hint).collect()
+}
+
+fn who(game : &Game, p_id : PlayerId) -> String {
+    game.get_name(p_id).filter(|n| !n.is_empty()).unwrap_or_else(|| format!("player {}", p_id.0))
+}
+
+/// scores follow the counting bot , a foul hands the whole trick to whoever is on top
+fn classic(game : &Game, me : PlayerId, hand : &[Card], card : &Card) -> (i32, Hint) {
+    let rules = &game.get_settings().rules;
+    let value = |c : &Card| rules.rank_value(c.rank) as i32;
+    let knowledge = game.get_knowledge();
+    let others = still_to_play(game, me);
+    let name = card_name(card);
+    let suit = suit_symbol(card.suit);
+    let hint = |score : i32, reason : String| (score, Hint { card : *card, reason });
+
+    let Some((led, _)) = game.get_trick().first().copied() else {
+        if game.is_opening() && rules.ace_of_spades_opens {
+            return hint(0, format!("the game opens with {name}"));
+        }
+        if let Some(&void) = others.iter().find(|&&p| knowledge.is_void(p, card.suit)) {
+            return hint(-100 - value(card), format!("{} is out of {suit} and could foul you", who(game, void)));
+        }
+        let held : Vec<&Card> = hand.iter().filter(|c| c.suit == card.suit).collect();
+        if held.len() == 1 {
+            return hint(60 - value(card), format!("lead your last {suit} to be void in it"));
+        }
+        if held.iter().any(|c| value(c) < value(card)) {
+            return hint(-value(card), format!("lead a lower {suit} , a foul on {name} lands on you"));
+        }
+        return hint(50 - value(card) - held.len() as i32, format!("lead {suit} low , everyone still has to follow"));
+    };
+
+    let led_suit = suit_symbol(led.suit);
+    if !hand.iter().any(|c| c.suit == led.suit) {
+        return hint(value(card), format!("void in {led_suit} — you can dump your {name}"));
+    }
+
+    let top = game.get_trick().iter().filter(|(c, _)| c.suit == led.suit).map(|(c, _)| *c).max_by_key(value).unwrap_or(led);
+    let foulers : Vec<PlayerId> = others.iter().copied().filter(|&p| knowledge.is_void(p, led.suit)).collect();
+    if others.is_empty() {
+        return hint(value(card), format!("last to play , the trick is discarded — get rid of your {name}"));
+    }
+    if value(card) < value(&top) {
+        return hint(50 + value(card), format!("follow {led_suit} low to avoid winning the trick"));
+    }
+    match foulers.first() {
+        Some(&p) => hint(-value(card), format!("beats {} , {} is out of {led_suit} and could foul you", card_name(&top), who(game, p))),
+        None => hint(20 - value(card), format!("beats {} , the lower the better in case someone behind you is out of {led_suit}", card_name(&top))),
+    }
+}
+
+/// hearts , taking tricks only brings points
+fn stay_low(game : &Game, hand : &[Card], card : &Card) -> (i32, Hint) {
+    let value = |c : &Card| c.rank as i32;
+    let name = card_name(card);
+    let suit = suit_symbol(card.suit);
+    let hint = |score : i32, reason : String| (score, Hint { card : *card, reason });
+
+    let Some((led, _)) = game.get_trick().first().copied() else {
+        return hint(-value(card), format!("lead {suit} low so someone else takes the trick"));
+    };
+    let led_suit = suit_symbol(led.suit);
+    if !hand.iter().any(|c| c.suit == led.suit) {
+        return hint(value(card), format!("void in {led_suit} — you can dump your {name}"));
+    }
+    let top = game.get_trick().iter().filter(|(c, _)| c.suit == led.suit).map(|(c, _)| value(c)).max().unwrap_or(0);
+    if value(card) < top {
+        return hint(50 + value(card), format!("follow {led_suit} low to avoid winning the trick"));
+    }
+    hint(-value(card), format!("{name} wins the trick so far"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::actions::Action;
+    use crate::game::settings::RoomSettings;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    /// three seats dealt `hands` with seat 0 leading , no A♠ so any card may open
+    fn table(settings : RoomSettings, hands : Vec<Vec<Card>>) -> Game {
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, ..settings });
+        for _ in 0..3 {
+            game.add_player();
+        }
+        game.set_preset_hands(hands, 0);
+        game.start_game();
+        game
+    }
+
+    fn nine_of_spades_led(second : Vec<Card>) -> Game {
+        let mut game = table(RoomSettings::default(), vec![
+            vec![card(Rank::Nine, Suit::Spade), card(Rank::Two, Suit::Club)],
+            second,
+            vec![card(Rank::Four, Suit::Spade), card(Rank::Four, Suit::Diamond)],
+        ]);
+        game.apply_action(Action::CardPlayedByPlayer { player_id : PlayerId(0), card : card(Rank::Nine, Suit::Spade), suit : None });
+        game
+    }
+
+    #[test]
+    fn following_under_the_trick_comes_first() {
+        let game = nine_of_spades_led(vec![card(Rank::King, Suit::Spade), card(Rank::Three, Suit::Spade), card(Rank::Five, Suit::Heart)]);
+        let hints = game.hints(PlayerId(1)).expect("on turn with hints on");
+
+        let cards : Vec<Card> = hints.iter().map(|h| h.card).collect();
+        assert_eq!(cards, vec![card(Rank::Three, Suit::Spade), card(Rank::King, Suit::Spade)]);
+        assert_eq!(hints[0].reason, "follow ♠ low to avoid winning the trick");
+        assert!(hints[1].reason.starts_with("beats 9♠"));
+    }
+
+    #[test]
+    fn void_in_the_led_suit_dumps_the_highest() {
+        let game = nine_of_spades_led(vec![card(Rank::Three, Suit::Diamond), card(Rank::King, Suit::Heart)]);
+        let hints = game.hints(PlayerId(1)).expect("on turn with hints on");
+
+        assert_eq!(hints[0].card, card(Rank::King, Suit::Heart));
+        assert_eq!(hints[0].reason, "void in ♠ — you can dump your K♥");
+    }
+
+    #[test]
+    fn hints_turned_off_are_an_error() {
+        let settings = RoomSettings { hints : false, ..RoomSettings::default() };
+        let game = table(settings, vec![
+            vec![card(Rank::Nine, Suit::Spade)],
+            vec![card(Rank::Three, Suit::Spade)],
+            vec![card(Rank::Four, Suit::Spade)],
+        ]);
+        assert_eq!(game.hints(PlayerId(0)).map(|h| h.len()), Err("hints are turned off in this room".into()));
+    }
+
+    #[test]
+    fn only_the_player_on_turn_gets_hints() {
+        let game = nine_of_spades_led(vec![card(Rank::Three, Suit::Spade)]);
+        assert!(game.hints(PlayerId(2)).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::time::{Duration, Instant};
+use rand::Rng;
+use crate::bot::Bot;
+use crate::bot::simple::SimpleBot;
+use crate::game::actions::Action;
+use crate::game::card::{Card, shuffle, standard_deck};
+use crate::game::event::Event;
+use crate::game::logic::{Game, GamePhase};
+use crate::game::players::PlayerId;
+use crate::game::settings::{GameMode, SearchBudget};
+use crate::game::rng;
+
+/// how much unexplored moves are favoured over ones that did well so far
+const EXPLORATION : f64 = 0.7;
+/// a playout still going after this many cards is scored as it stands
+const MAX_PLAYOUT : usize = 400;
+
+/// one move in the search tree , the same card may be playable in some guesses and not in others
+struct Node {
+    /// who played `card` to get here , None at the root
+    mover : Option<PlayerId>,
+    card : Option<Card>,
+    parent : Option<usize>,
+    children : Vec<usize>,
+    visits : u32,
+    /// times this node could have been picked
+    avail : u32,
+    /// finishing score of `mover` summed over the visits
+    reward : f64,
+}
+
+impl Node {
+    fn new(mover : Option<PlayerId>, card : Option<Card>, parent : Option<usize>) -> Self {
+        Self { mover, card, parent, children : Vec::new(), visits : 0, avail : 0, reward : 0.0 }
+    }
+
+    fn ucb(&self) -> f64 {
+        let visits = self.visits.max(1) as f64;
+        self.reward / visits + EXPLORATION * ((self.avail.max(1) as f64).ln() / visits).sqrt()
+    }
+}
+
+/// who got out in what order during a playout
+#[derive(Default)]
+struct Outcome {
+    order : Vec<PlayerId>,
+    loser : Option<PlayerId>,
+}
+
+impl Outcome {
+    fn record(&mut self, events : &[Event]) {
+        for ev in events {
+            match ev {
+                Event::PlayerWon { player_id } => self.order.push(*player_id),
+                Event::EndGame { p_id } => self.loser = Some(*p_id),
+                _ => {}
+            }
+        }
+    }
+
+    fn is_done(&self, p_id : PlayerId) -> bool {
+        self.order.contains(&p_id) || self.loser == Some(p_id)
+    }
+
+    /// 1 for going out first down to 0 for losing , players still holding cards share the places left
+    fn score(&self, p_id : PlayerId, players : usize) -> f64 {
+        let last = players.saturating_sub(1).max(1) as f64;
+        if let Some(place) = self.order.iter().position(|&p| p == p_id) {
+            return 1.0 - place as f64 / last;
+        }
+        if self.loser == Some(p_id) {
+            return 0.0;
+        }
+        let first_open = self.order.len() as f64;
+        let last_open = if self.loser.is_some() { last - 1.0 } else { last };
+        1.0 - (first_open + last_open) / 2.0 / last
+    }
+}
+
+/// information set monte carlo tree search for the classic game
+/// deals the cards it can't see in a way that fits everything seen so far and plays the guesses out ,
+/// the card with the best average finishing place wins
+pub struct IsmctsBot {
+    budget : SearchBudget,
+}
+
+impl IsmctsBot {
+    pub fn new(budget : SearchBudget) -> Self {
+        Self { budget }
+    }
+
+    /// a copy of `game` with the hidden cards dealt at random , None if what was seen doesn't add up
+    fn guess(game : &Game, me : PlayerId) -> Option<Game> {
+        let knowledge = game.get_knowledge();
+        let mine = game.get_hand(me)?;
+        let table : Vec<Card> = game.get_trick().iter().map(|(c, _)| *c).collect();
+        let mut others : Vec<PlayerId> = game.get_seats().into_iter().filter(|&p| p != me).collect();
+
+        let mut pool : Vec<Card> = standard_deck()
+            .into_iter()
+            .filter(|c| !mine.contains(c) && !table.contains(c) && !knowledge.is_gone(c))
+            .filter(|c| !others.iter().any(|&p| knowledge.get_known(p).contains(c)))
+            .collect();
+        shuffle(&mut pool);
+
+        let unknown = |p : PlayerId| game.hand_len(p).checked_sub(knowledge.get_known(p).len());
+        let mut needed = 0;
+        for &p in &others {
+            needed += unknown(p)?;
+        }
+        if needed != pool.len() {
+            return None;
+        }
+
+        // players with the most voids pick first while there are still cards left that fit
+        let voids = |p : PlayerId| pool.iter().filter(|c| knowledge.is_void(p, c.suit)).count();
+        others.sort_by_key(|&p| std::cmp::Reverse(voids(p)));
+
+        let mut copy = game.clone();
+        for p in others {
+            let count = unknown(p)?;
+            let mut hand = knowledge.get_known(p).to_vec();
+            let mut i = 0;
+            while hand.len() < knowledge.get_known(p).len() + count && i < pool.len() {
+                if knowledge.is_void(p, pool[i].suit) {
+                    i += 1;
+                } else {
+                    hand.push(pool.remove(i));
+                }
+            }
+            // nothing fitting left , hand out whatever there is
+            while hand.len() < knowledge.get_known(p).len() + count {
+                hand.push(pool.pop()?);
+            }
+            copy.set_hand(p, hand);
+        }
+        Some(copy)
+    }
+
+    /// one guess played through the tree and then out to the end
+    fn iterate(tree : &mut Vec<Node>, mut game : Game, me : PlayerId) {
+        let players = game.get_seats().len();
+        let mut outcome = Outcome::default();
+        let mut node = 0;
+
+        // down the tree while every playable card has been tried
+        while game.get_phase() == GamePhase::Playing && !outcome.is_done(me) {
+            let p_id = game.get_turn();
+            let legal = game.legal_cards(p_id);
+            if legal.is_empty() {
+                break;
+            }
+            let children : Vec<usize> = tree[node].children
+                .iter()
+                .copied()
+                .filter(|&c| tree[c].mover == Some(p_id) && tree[c].card.is_some_and(|card| legal.contains(&card)))
+                .collect();
+            for &c in &children {
+                tree[c].avail += 1;
+            }
+
+            let untried : Vec<Card> = legal.into_iter().filter(|card| !children.iter().any(|&c| tree[c].card == Some(*card))).collect();
+            let next = if untried.is_empty() {
+                children.into_iter().max_by(|&a, &b| tree[a].ucb().total_cmp(&tree[b].ucb())).expect("a legal card was tried")
+            } else {
+                let card = untried[rng::with(|rng| rng.random_range(0..untried.len()))];
+                tree.push(Node::new(Some(p_id), Some(card), Some(node)));
+                let child = tree.len() - 1;
+                tree[child].avail = 1;
+                tree[node].children.push(child);
+                child
+            };
+
+            let card = tree[next].card.expect("only the root has no card");
+            outcome.record(&game.apply_action(Action::CardPlayedByPlayer { player_id : p_id, card, suit : None }));
+            node = next;
+            if !untried.is_empty() {
+                break;
+            }
+        }
+
+        // the rest is played out by the simple bot for everyone
+        let mut playout = SimpleBot::default();
+        let mut plays = 0;
+        while game.get_phase() == GamePhase::Playing && !outcome.is_done(me) && plays < MAX_PLAYOUT {
+            let p_id = game.get_turn();
+            let Some(card) = playout.choose_card(&game, p_id) else {
+                break;
+            };
+            outcome.record(&game.apply_action(Action::CardPlayedByPlayer { player_id : p_id, card, suit : None }));
+            plays += 1;
+        }
+
+        let mut at = Some(node);
+        while let Some(i) = at {
+            tree[i].visits += 1;
+            if let Some(mover) = tree[i].mover {
+                tree[i].reward += outcome.score(mover, players);
+            }
+            at = tree[i].parent;
+        }
+    }
+}
+
+impl Bot for IsmctsBot {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        let legal = game.legal_cards(me);
+        if legal.len() < 2 || game.get_settings().mode != GameMode::Classic {
+            return SimpleBot::default().choose_card(game, me);
+        }
+
+        let started = Instant::now();
+        let mut tree = vec![Node::new(None, None, None)];
+        let mut iterations = 0;
+        loop {
+            let done = match self.budget {
+                SearchBudget::Iterations(n) => iterations >= n,
+                SearchBudget::Millis(ms) => started.elapsed() >= Duration::from_millis(ms),
+            };
+            if done {
+                break;
+            }
+            let Some(guess) = Self::guess(game, me) else {
+                return SimpleBot::default().choose_card(game, me);
+            };
+            Self::iterate(&mut tree, guess, me);
+            iterations += 1;
+        }
+
+        // the most visited card is the one the search trusts most
+        tree[0].children
+            .iter()
+            .max_by_key(|&&c| tree[c].visits)
+            .and_then(|&c| tree[c].card)
+            .or_else(|| SimpleBot::default().choose_card(game, me))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::game::card::Suit;
+    use crate::game::settings::RoomSettings;
+
+    const SUITS : [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
+
+    /// a seeded three seat game played by the simple bot until someone besides seat 0 showed a void
+    /// and a trick was thrown away
+    fn game_with_a_void() -> Game {
+        rng::seed(Some(11));
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, ..RoomSettings::default() });
+        for _ in 0..3 {
+            game.add_player();
+        }
+        game.start_game();
+
+        let mut bot = SimpleBot::default();
+        let shown = |game : &Game| {
+            let k = game.get_knowledge();
+            let void = game.get_seats().into_iter().skip(1).any(|p| SUITS.iter().any(|&s| k.is_void(p, s)));
+            void && standard_deck().iter().any(|c| k.is_gone(c))
+        };
+        while !shown(&game) {
+            assert_eq!(game.get_phase(), GamePhase::Playing, "the game ended before a void showed");
+            let p_id = game.get_turn();
+            let card = bot.choose_card(&game, p_id).expect("the player on turn has a card");
+            game.apply_action(Action::CardPlayedByPlayer { player_id : p_id, card, suit : None });
+        }
+        rng::seed(None);
+        game
+    }
+
+    #[test]
+    fn guesses_fit_everything_seen() {
+        let game = game_with_a_void();
+        let me = PlayerId(0);
+        let k = game.get_knowledge();
+        let others : Vec<PlayerId> = game.get_seats().into_iter().filter(|&p| p != me).collect();
+        let hidden : HashSet<Card> = others.iter().flat_map(|&p| game.get_hand(p).unwrap()).collect();
+
+        rng::seed(Some(12));
+        for _ in 0..50 {
+            let guess = IsmctsBot::guess(&game, me).expect("what was seen adds up");
+            assert_eq!(guess.get_hand(me), game.get_hand(me));
+
+            let mut dealt = HashSet::new();
+            for &p in &others {
+                let hand = guess.get_hand(p).unwrap();
+                assert_eq!(hand.len(), game.hand_len(p));
+                assert!(k.get_known(p).iter().all(|c| hand.contains(c)));
+                for c in hand.iter().filter(|c| !k.get_known(p).contains(c)) {
+                    assert!(!k.is_void(p, c.suit), "{p:?} is void in {:?} but got {c}", c.suit);
+                }
+                dealt.extend(hand);
+            }
+            // only the cards still hidden are handed out , nothing played , thrown away or mine
+            assert_eq!(dealt, hidden);
+        }
+        rng::seed(None);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::game::actions::Action;
+use crate::game::card::{Card, Suit};
+use crate::game::event::Event;
+use crate::game::hearts::suggested_pass;
+use crate::game::callbreak::suggested_bid;
+use crate::game::logic::{Game, GamePhase};
+use crate::game::players::PlayerId;
+
+pub mod simple;
+pub mod random;
+pub mod counting;
+pub mod ismcts;
+pub mod profile;
+pub mod engine;
+pub mod hint;
+pub mod rating;
+
+/// a game still going after this many cards is cut short
+pub const MAX_PLAYS : u32 = 5000;
+
+/// something that can take a seat and pick cards for it
+pub trait Bot : Send {
+    /// the card to play for `me` , only called when it is `me`'s turn
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card>;
+
+    /// the cards to pass in hearts , only called while `me` still has to pass
+    fn choose_pass(&mut self, game : &Game, me : PlayerId) -> Vec<Card> {
+        suggested_pass(&game.get_hand(me).unwrap_or_default())
+    }
+
+    /// the callbreak bid , only called when it is `me`'s turn to bid
+    fn choose_bid(&mut self, game : &Game, me : PlayerId) -> u32 {
+        suggested_bid(&game.get_hand(me).unwrap_or_default())
+    }
+
+    /// every event broadcast at the table , in order , for bots that keep their own picture of the game
+    fn observe(&mut self, _ev : &Event) {}
+}
+
+/// the cards of `legal` in the suit `hand` holds fewest of , playing them out leaves a void to foul with
+pub fn shortest_suit(hand : &[Card], legal : &[Card]) -> Vec<Card> {
+    let count = |suit : Suit| hand.iter().filter(|c| c.suit == suit).count();
+    let Some(suit) = legal.iter().map(|c| c.suit).min_by_key(|&s| count(s)) else {
+        return vec![];
+    };
+    legal.iter().copied().filter(|c| c.suit == suit).collect()
+}
+
+/// players still to play to the current trick after `me`
+pub(crate) fn still_to_play(game : &Game, me : PlayerId) -> Vec<PlayerId> {
+    let seats = game.get_seats();
+    let Some(pos) = seats.iter().position(|&p| p == me) else {
+        return vec![];
+    };
+    let played : Vec<PlayerId> = game.get_trick().iter().map(|(_, p)| *p).collect();
+    (1..seats.len())
+        .map(|i| seats[(pos + i) % seats.len()])
+        .filter(|p| !game.is_finished(*p) && !played.contains(p))
+        .collect()
+}
+
+/// bots play their turns until the game is over , `MAX_PLAYS` cards are down , `stop` says so ,
+/// a seat without a bot is on turn or a bot has no card. `plays` counts the cards ,
+/// every event goes to all `bots` and to `seen`
+pub fn play_bots(game : &mut Game, bots : &mut [(PlayerId, Box<dyn Bot>)], plays : &mut u32, stop : impl Fn(&Game) -> bool, mut seen : impl FnMut(&Event)) {
+    while game.get_phase() == GamePhase::Playing && *plays < MAX_PLAYS && !stop(game) {
+        let turn = game.get_turn();
+        let Some((_, bot)) = bots.iter_mut().find(|(p, _)| *p == turn) else {
+            return;
+        };
+        let Some(card) = bot.choose_card(game, turn) else {
+            return;
+        };
+        *plays += 1;
+
+        for ev in game.apply_action(Action::CardPlayedByPlayer { player_id : turn, card, suit : None }) {
+            bots.iter_mut().for_each(|(_, bot)| bot.observe(&ev));
+            seen(&ev);
+        }
+    }
+}
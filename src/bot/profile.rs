@@ -0,0 +1,138 @@
+use std::str::FromStr;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use crate::bot::Bot;
+use crate::bot::counting::CountingBot;
+use crate::bot::ismcts::IsmctsBot;
+use crate::bot::random::RandomBot;
+use crate::bot::simple::SimpleBot;
+use crate::game::settings::SearchBudget;
+use crate::game::rng;
+
+/// how well a bot plays , the host picks one for every bot seat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// any legal card
+    Random,
+    /// low when following suit , high when fouling
+    #[default]
+    Greedy,
+    /// remembers what was played and who is void in what
+    Counting,
+    /// plays guesses of the hidden hands out , classic games only
+    Search,
+}
+
+/// the names the command line tools take , any case
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(name : &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "random" => Ok(Difficulty::Random),
+            "greedy" => Ok(Difficulty::Greedy),
+            "counting" => Ok(Difficulty::Counting),
+            "search" => Ok(Difficulty::Search),
+            _ => Err(format!("no bot called {}", name.trim())),
+        }
+    }
+}
+
+/// leanings that change how a bot plays , each is the chance of acting on it from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Personality {
+    /// get rid of the highest card when following suit instead of the lowest
+    pub dump_high : f64,
+    /// lead and foul from the shortest suit , emptying it to foul others with later
+    pub keep_voids : f64,
+}
+
+impl Personality {
+    pub fn dumps_high(&self) -> bool {
+        rng::with(|rng| rng.random::<f64>()) < self.dump_high
+    }
+
+    pub fn keeps_voids(&self) -> bool {
+        rng::with(|rng| rng.random::<f64>()) < self.keep_voids
+    }
+}
+
+/// everything that makes up a bot seat
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BotProfile {
+    pub difficulty : Difficulty,
+    pub personality : Personality,
+    /// an outside program from the server's engine list plays the seat , `difficulty` covers for it when it fails
+    pub engine : Option<String>,
+}
+
+impl BotProfile {
+    pub fn validate(&self) -> Result<(), String> {
+        let traits = [self.personality.dump_high, self.personality.keep_voids];
+        if traits.iter().any(|t| !(0.0..=1.0).contains(t)) {
+            return Err("personality traits must be between 0 and 1".into());
+        }
+        if self.engine.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            return Err("engine name is empty".into());
+        }
+        Ok(())
+    }
+
+    /// the bot playing this seat , `search` is the room's budget for searching bots
+    pub fn make(&self, search : SearchBudget) -> Box<dyn Bot> {
+        match self.difficulty {
+            Difficulty::Random => Box::new(RandomBot),
+            Difficulty::Greedy => Box::new(SimpleBot::new(self.personality)),
+            Difficulty::Counting => Box::new(CountingBot::new(self.personality)),
+            Difficulty::Search => Box::new(IsmctsBot::new(search)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// how many of 1000 draws `leaning` acted on
+    fn acted(leaning : impl Fn() -> bool) -> usize {
+        rng::seed(Some(7));
+        let n = (0..1000).filter(|_| leaning()).count();
+        rng::seed(None);
+        n
+    }
+
+    #[test]
+    fn leanings_at_the_ends_are_never_or_always() {
+        let never = Personality::default();
+        let always = Personality { dump_high : 1.0, keep_voids : 1.0 };
+        assert_eq!(acted(|| never.dumps_high()), 0);
+        assert_eq!(acted(|| never.keeps_voids()), 0);
+        assert_eq!(acted(|| always.dumps_high()), 1000);
+        assert_eq!(acted(|| always.keeps_voids()), 1000);
+    }
+
+    #[test]
+    fn leanings_act_about_as_often_as_their_chance() {
+        let personality = Personality { dump_high : 0.25, keep_voids : 0.75 };
+        assert!((200..300).contains(&acted(|| personality.dumps_high())));
+        assert!((700..800).contains(&acted(|| personality.keeps_voids())));
+    }
+
+    #[test]
+    fn difficulties_parse_in_any_case() {
+        assert_eq!(" Counting ".parse(), Ok(Difficulty::Counting));
+        assert_eq!("SEARCH".parse(), Ok(Difficulty::Search));
+        assert!("expert".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn traits_outside_zero_to_one_are_refused() {
+        let profile = |dump_high| BotProfile { personality : Personality { dump_high, keep_voids : 0.5 }, ..BotProfile::default() };
+        assert!(profile(0.0).validate().is_ok());
+        assert!(profile(1.0).validate().is_ok());
+        assert!(profile(1.5).validate().is_err());
+        assert!(profile(-0.1).validate().is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use rand::Rng;
+use crate::bot::Bot;
+use crate::game::card::Card;
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+use crate::game::rng;
+
+/// any legal card , for tables that want an easy game
+pub struct RandomBot;
+
+impl Bot for RandomBot {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        let legal = game.legal_cards(me);
+        if legal.is_empty() {
+            return None;
+        }
+        Some(legal[rng::with(|rng| rng.random_range(0..legal.len()))])
+    }
+}
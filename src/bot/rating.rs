@@ -0,0 +1,60 @@
+/// z for a 95% confidence interval
+pub const Z : f64 = 1.96;
+
+/// mean and the half width of its 95% interval , too few samples give the widest interval round a coin flip
+pub fn interval(samples : &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    if n < 2.0 {
+        return (samples.first().copied().unwrap_or(0.5), 0.5);
+    }
+    let mean = samples.iter().sum::<f64>() / n;
+    let var = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, Z * (var / n).sqrt())
+}
+
+/// elo difference that expects `score` , kept finite for clean sweeps
+pub fn elo(score : f64) -> f64 {
+    let score = score.clamp(0.001, 0.999);
+    400.0 * (score / (1.0 - score)).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(x : f64, y : f64) -> bool {
+        (x - y).abs() < 1e-9
+    }
+
+    #[test]
+    fn interval_of_deal_scores() {
+        let (mean, half) = interval(&[0.0, 1.0]);
+        assert!(close(mean, 0.5));
+        assert!(close(half, Z * 0.5));
+
+        // the same score every deal is sure
+        let (mean, half) = interval(&[0.75; 10]);
+        assert!(close(mean, 0.75) && close(half, 0.0));
+
+        // more deals , narrower interval
+        let few : Vec<f64> = [0.0, 1.0].repeat(5);
+        let many : Vec<f64> = [0.0, 1.0].repeat(50);
+        assert!(interval(&many).1 < interval(&few).1);
+    }
+
+    #[test]
+    fn one_deal_or_none_says_nothing() {
+        assert_eq!(interval(&[]), (0.5, 0.5));
+        assert_eq!(interval(&[1.0]), (1.0, 0.5));
+    }
+
+    #[test]
+    fn elo_of_a_score() {
+        assert!(close(elo(0.5), 0.0));
+        assert!(close(elo(0.75), 400.0 * 3f64.log10()));
+        assert!(close(elo(0.2), -elo(0.8)));
+        // sweeps stay finite and clamp to the same number
+        assert!(elo(1.0).is_finite() && close(elo(1.0), elo(0.999)));
+        assert!(close(elo(0.0), -elo(1.0)));
+    }
+}
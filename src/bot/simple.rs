@@ -0,0 +1,85 @@
+use crate::bot::{Bot, shortest_suit};
+use crate::bot::profile::Personality;
+use crate::game::card::Card;
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+
+/// plays low while it has to follow suit and dumps its highest card when it can foul
+#[derive(Default)]
+pub struct SimpleBot {
+    personality : Personality,
+}
+
+impl SimpleBot {
+    pub fn new(personality : Personality) -> Self {
+        Self { personality }
+    }
+}
+
+impl Bot for SimpleBot {
+    fn choose_card(&mut self, game : &Game, me : PlayerId) -> Option<Card> {
+        let mut legal = game.legal_cards(me);
+        let hand = game.get_hand(me)?;
+
+        // void in the led suit , this card goes to whoever is winning the trick
+        let fouling = game.get_trick().first().is_some_and(|(led, _)| hand.iter().all(|c| c.suit != led.suit));
+        let leading = game.get_trick().is_empty();
+
+        if (leading || fouling) && self.personality.keeps_voids() {
+            legal = shortest_suit(&hand, &legal);
+        }
+
+        let rules = &game.get_settings().rules;
+        if fouling || (!leading && self.personality.dumps_high()) {
+            legal.into_iter().max_by_key(|c| rules.rank_value(c.rank))
+        } else {
+            legal.into_iter().min_by_key(|c| rules.rank_value(c.rank))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::actions::Action;
+    use crate::game::card::{Rank, Suit};
+    use crate::game::settings::{GameMode, RoomSettings, CALLBREAK_PLAYERS};
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    /// A♥ led and trumped , the third seat still holds hearts but no spades
+    fn hearts_led_and_trumped() -> (Game, PlayerId) {
+        let mut game = Game::with_settings(RoomSettings { mode : GameMode::Callbreak, max_players : CALLBREAK_PLAYERS, ..RoomSettings::default() });
+        for _ in 0..CALLBREAK_PLAYERS {
+            game.add_player();
+        }
+        game.start_game();
+        while game.is_bidding() {
+            game.apply_action(Action::Bid { player_id : game.get_turn(), bid : 2 });
+        }
+
+        let seats = game.get_seats();
+        let lead = seats.iter().position(|&p| p == game.get_turn()).unwrap();
+        let seat = |i : usize| seats[(lead + i) % seats.len()];
+        game.set_hand(seat(0), vec![card(Rank::Ace, Suit::Heart), card(Rank::Four, Suit::Club)]);
+        game.set_hand(seat(1), vec![card(Rank::Two, Suit::Spade), card(Rank::Five, Suit::Club)]);
+        game.set_hand(seat(2), vec![card(Rank::King, Suit::Heart), card(Rank::Three, Suit::Heart), card(Rank::Five, Suit::Diamond)]);
+        game.set_hand(seat(3), vec![card(Rank::Queen, Suit::Heart), card(Rank::Six, Suit::Club)]);
+
+        for (i, c) in [(0, card(Rank::Ace, Suit::Heart)), (1, card(Rank::Two, Suit::Spade))] {
+            game.apply_action(Action::CardPlayedByPlayer { player_id : seat(i), card : c, suit : None });
+        }
+        (game, seat(2))
+    }
+
+    #[test]
+    fn follows_low_after_someone_else_went_off_suit() {
+        let (game, me) = hearts_led_and_trumped();
+        assert_eq!(game.get_turn(), me);
+
+        assert_eq!(SimpleBot::default().choose_card(&game, me), Some(card(Rank::Three, Suit::Heart)));
+        assert_eq!(game.hints(me).map(|hints| hints[0].card), Ok(card(Rank::Three, Suit::Heart)));
+    }
+}
@@ -0,0 +1,142 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use serde::Serialize;
+use crate::env::{Env, CARDS, OBSERVATION_SIZE};
+
+/// one agent move , as training data
+#[derive(Debug, Clone, Serialize)]
+pub struct Transition {
+    pub episode : u64,
+    pub step : u32,
+    /// what the agent saw before moving , the mask is its last `CARDS` entries
+    pub observation : Vec<f32>,
+    pub action : usize,
+    pub reward : f32,
+    pub done : bool,
+    pub truncated : bool,
+}
+
+/// picks the agent's action from the observation and the legal move mask
+pub type Policy<'a> = dyn FnMut(&Env, &[f32], &[bool]) -> usize + 'a;
+
+/// plays one episode from `seed` with `policy` moving for the agent
+pub fn rollout(env : &mut Env, episode : u64, seed : u64, policy : &mut Policy) -> Result<Vec<Transition>, String> {
+    let mut observation = env.reset(seed);
+    let mut transitions = Vec::new();
+    for step in 0.. {
+        let mask = env.action_mask(env.get_agent());
+        let action = policy(env, &observation, &mask);
+        let next = env.step(action)?;
+        let done = next.done;
+        transitions.push(Transition { episode, step, observation, action, reward : next.reward, done, truncated : next.truncated });
+        if done {
+            break;
+        }
+        observation = next.observation;
+    }
+    Ok(transitions)
+}
+
+/// one transition per line
+pub fn write_jsonl(path : &Path, transitions : &[Transition]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    for t in transitions {
+        serde_json::to_writer(&mut out, t).map_err(|e| e.to_string())?;
+        writeln!(out).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// `observations.npy` (f32 , N x OBSERVATION_SIZE) , `masks.npy` (bool , N x 52) , `actions.npy` (i64) ,
+/// `rewards.npy` (f32) , `dones.npy` (bool) , `truncated.npy` (bool) and `episodes.npy` (i64) inside the directory `dir`
+pub fn write_npy(dir : &Path, transitions : &[Transition]) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    let n = transitions.len();
+
+    let observations : Vec<u8> = transitions.iter().flat_map(|t| t.observation.iter().flat_map(|x| x.to_le_bytes())).collect();
+    let masks : Vec<u8> = transitions.iter().flat_map(|t| t.observation[OBSERVATION_SIZE - CARDS..].iter().map(|&x| (x > 0.0) as u8)).collect();
+    let actions : Vec<u8> = transitions.iter().flat_map(|t| (t.action as i64).to_le_bytes()).collect();
+    let rewards : Vec<u8> = transitions.iter().flat_map(|t| t.reward.to_le_bytes()).collect();
+    let dones : Vec<u8> = transitions.iter().map(|t| t.done as u8).collect();
+    let truncated : Vec<u8> = transitions.iter().map(|t| t.truncated as u8).collect();
+    let episodes : Vec<u8> = transitions.iter().flat_map(|t| (t.episode as i64).to_le_bytes()).collect();
+
+    write_array(&dir.join("observations.npy"), "<f4", &[n, OBSERVATION_SIZE], &observations)?;
+    write_array(&dir.join("masks.npy"), "|b1", &[n, CARDS], &masks)?;
+    write_array(&dir.join("actions.npy"), "<i8", &[n], &actions)?;
+    write_array(&dir.join("rewards.npy"), "<f4", &[n], &rewards)?;
+    write_array(&dir.join("dones.npy"), "|b1", &[n], &dones)?;
+    write_array(&dir.join("truncated.npy"), "|b1", &[n], &truncated)?;
+    write_array(&dir.join("episodes.npy"), "<i8", &[n], &episodes)
+}
+
+/// a version 1.0 `.npy` file , the header is padded so the data starts on a 64 byte boundary
+fn write_array(path : &Path, descr : &str, shape : &[usize], data : &[u8]) -> Result<(), String> {
+    let shape = match shape {
+        [n] => format!("({n},)"),
+        _ => format!("({})", shape.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+    // magic (6) , version (2) , header length (2) , then the header ending in a newline
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let file = File::create(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    out.write_all(b"\x93NUMPY\x01\x00")
+        .and_then(|_| out.write_all(&(header.len() as u16).to_le_bytes()))
+        .and_then(|_| out.write_all(header.as_bytes()))
+        .and_then(|_| out.write_all(data))
+        .and_then(|_| out.flush())
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npy_header_is_padded_to_64_bytes() {
+        let path = std::env::temp_dir().join(format!("card_game_npy_{}.npy", std::process::id()));
+        let data : Vec<u8> = [1.5f32, -2.0, 0.25].iter().flat_map(|x| x.to_le_bytes()).collect();
+        write_array(&path, "<f4", &[3], &data).expect("temp dir is writable");
+        let bytes = std::fs::read(&path).expect("just written");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + len]).expect("header is ascii");
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(&bytes[10 + len..], data.as_slice());
+    }
+
+    #[test]
+    fn npy_export_keeps_the_truncated_flag() {
+        let dir = std::env::temp_dir().join(format!("card_game_npy_dir_{}", std::process::id()));
+        let transition = |truncated| Transition { episode : 0, step : 0, observation : vec![0.0; OBSERVATION_SIZE], action : 0, reward : 0.0, done : true, truncated };
+        write_npy(&dir, &[transition(false), transition(true)]).expect("temp dir is writable");
+        let bytes = std::fs::read(dir.join("truncated.npy")).expect("just written");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert!(std::str::from_utf8(&bytes[10..10 + len]).expect("header is ascii").contains("'shape': (2,)"));
+        assert_eq!(&bytes[10 + len..], &[0, 1]);
+    }
+
+    #[test]
+    fn npy_shape_lists_every_dimension() {
+        let path = std::env::temp_dir().join(format!("card_game_npy_2d_{}.npy", std::process::id()));
+        write_array(&path, "|b1", &[2, 3], &[0; 6]).expect("temp dir is writable");
+        let bytes = std::fs::read(&path).expect("just written");
+        let _ = std::fs::remove_file(&path);
+
+        let len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert!(std::str::from_utf8(&bytes[10..10 + len]).expect("header is ascii").contains("'shape': (2, 3)"));
+        assert_eq!(bytes.len(), 10 + len + 6);
+    }
+}
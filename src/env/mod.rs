@@ -0,0 +1,307 @@
+use rand::{SeedableRng, rngs::StdRng};
+use crate::bot::{Bot, MAX_PLAYS, play_bots};
+use crate::bot::profile::{BotProfile, Difficulty};
+use crate::game::actions::Action;
+use crate::game::card::{Card, Rank, Suit};
+use crate::game::event::Event;
+use crate::game::logic::{Game, GamePhase};
+use crate::game::players::PlayerId;
+use crate::game::rng;
+use crate::game::rules::HouseRules;
+use crate::game::settings::{RoomSettings, SearchBudget, MAX_PLAYERS};
+
+pub mod export;
+
+pub const CARDS : usize = 52;
+const SUITS : [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
+const RANKS : [Rank; 13] = [
+    Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+    Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+];
+
+/// length of `Env::observation` , the parts in order :
+/// own hand , current trick , discarded cards (52 each) , led suit (4) ,
+/// cards known to be held and suits known void for each other seat counting from the left (52 + 4 each) ,
+/// cards held and finished for every seat counting from `me` (1 + 1 each) , legal move mask (52)
+pub const OBSERVATION_SIZE : usize = 3 * CARDS + 4 + (MAX_PLAYERS - 1) * (CARDS + 4) + 2 * MAX_PLAYERS + CARDS;
+
+/// action index of a card , suits in `S H D C` order and 2 up to A inside each
+pub fn card_index(card : &Card) -> usize {
+    let suit = SUITS.iter().position(|&s| s == card.suit).expect("every suit is listed");
+    suit * RANKS.len() + (card.rank as usize - Rank::Two as usize)
+}
+
+pub fn index_card(index : usize) -> Option<Card> {
+    let suit = *SUITS.get(index / RANKS.len())?;
+    Some(Card { rank : RANKS[index % RANKS.len()], suit })
+}
+
+/// what the environment plays
+#[derive(Debug, Clone)]
+pub struct EnvConfig {
+    /// seats at the table , the agent sits in the first one
+    pub players : usize,
+    /// the bots playing every other seat
+    pub opponents : BotProfile,
+    pub search : SearchBudget,
+    pub rules : HouseRules,
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        Self {
+            players : MAX_PLAYERS,
+            opponents : BotProfile { difficulty : Difficulty::Greedy, ..BotProfile::default() },
+            search : SearchBudget::Iterations(100),
+            rules : HouseRules::default(),
+        }
+    }
+}
+
+/// what `Env::step` hands back
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub observation : Vec<f32>,
+    /// 0 until the agent's game is decided , then 1 for going out first down to -1 for losing
+    pub reward : f32,
+    /// the agent is out or the game is over
+    pub done : bool,
+    /// the game hit the length limit before it was decided
+    pub truncated : bool,
+}
+
+/// the classic game as a training environment for one agent against bots
+/// the env keeps its own rng seeded by `reset` , the deal , the bots and the rescue only draw from it
+/// so an episode repeats whatever else uses the thread's rng in between
+pub struct Env {
+    config : EnvConfig,
+    rng : StdRng,
+    game : Game,
+    me : PlayerId,
+    bots : Vec<(PlayerId, Box<dyn Bot>)>,
+    /// who got out in order , and the loser once there is one
+    order : Vec<PlayerId>,
+    loser : Option<PlayerId>,
+    plays : u32,
+}
+
+impl Env {
+    pub fn new(config : EnvConfig) -> Self {
+        let mut env = Self { config, rng : StdRng::seed_from_u64(0), game : Game::new(), me : PlayerId(0), bots : Vec::new(), order : Vec::new(), loser : None, plays : 0 };
+        env.reset(0);
+        env
+    }
+
+    /// deals a new game from `seed` and lets the bots play up to the agent's first turn
+    pub fn reset(&mut self, seed : u64) -> Vec<f32> {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.seeded(Self::deal);
+        self.observation(self.me)
+    }
+
+    fn deal(&mut self) {
+        let settings = RoomSettings { max_players : self.config.players, rules : self.config.rules.clone(), bot_search : self.config.search, ..RoomSettings::default() };
+        self.game = Game::with_settings(settings);
+        for _ in 0..self.config.players {
+            self.game.add_bot(self.config.opponents.clone());
+        }
+        self.game.start_game();
+
+        let seats = self.game.get_seats();
+        self.me = seats[0];
+        self.bots = seats[1..].iter().map(|&p| (p, self.config.opponents.make(self.config.search))).collect();
+        self.order.clear();
+        self.loser = None;
+        self.plays = 0;
+
+        self.play_bots();
+    }
+
+    /// runs `f` with the env's rng standing in for the thread's , the thread gets its own back after
+    fn seeded<T>(&mut self, f : impl FnOnce(&mut Self) -> T) -> T {
+        let outer = rng::swap(Some(self.rng.clone()));
+        let out = f(self);
+        if let Some(inner) = rng::swap(outer) {
+            self.rng = inner;
+        }
+        out
+    }
+
+    pub fn get_agent(&self) -> PlayerId {
+        self.me
+    }
+
+    pub fn get_game(&self) -> &Game {
+        &self.game
+    }
+
+    /// the fixed size encoding of everything `player` may know , see `OBSERVATION_SIZE`
+    pub fn observation(&self, player : PlayerId) -> Vec<f32> {
+        let game = &self.game;
+        let knowledge = game.get_knowledge();
+        let mut obs = Vec::with_capacity(OBSERVATION_SIZE);
+
+        let mut cards = |set : &dyn Fn(&Card) -> bool| {
+            obs.extend((0..CARDS).map(|i| index_card(i).filter(|c| set(c)).map_or(0.0, |_| 1.0)));
+        };
+        let hand = game.get_hand(player).unwrap_or_default();
+        let trick : Vec<Card> = game.get_trick().iter().map(|(c, _)| *c).collect();
+        cards(&|c| hand.contains(c));
+        cards(&|c| trick.contains(c));
+        cards(&|c| knowledge.is_gone(c));
+
+        let led = trick.first().map(|c| c.suit);
+        obs.extend(SUITS.iter().map(|&s| if led == Some(s) { 1.0 } else { 0.0 }));
+
+        // seats from `player` onwards , empty seats are all zeros
+        let seats = game.get_seats();
+        let pos = seats.iter().position(|&p| p == player).unwrap_or(0);
+        let around : Vec<Option<PlayerId>> = (0..MAX_PLAYERS).map(|i| (i < seats.len()).then(|| seats[(pos + i) % seats.len()])).collect();
+
+        for seat in &around[1..] {
+            let known = seat.map(|p| knowledge.get_known(p).to_vec()).unwrap_or_default();
+            obs.extend((0..CARDS).map(|i| index_card(i).filter(|c| known.contains(c)).map_or(0.0, |_| 1.0)));
+            obs.extend(SUITS.iter().map(|&s| if seat.is_some_and(|p| knowledge.is_void(p, s)) { 1.0 } else { 0.0 }));
+        }
+        for seat in &around {
+            obs.push(seat.map_or(0.0, |p| game.hand_len(p) as f32 / CARDS as f32));
+            obs.push(if seat.is_some_and(|p| game.is_finished(p)) { 1.0 } else { 0.0 });
+        }
+
+        obs.extend(self.action_mask(player).into_iter().map(|legal| if legal { 1.0 } else { 0.0 }));
+        obs
+    }
+
+    /// true for every action index `player` may play right now
+    pub fn action_mask(&self, player : PlayerId) -> Vec<bool> {
+        let mut mask = vec![false; CARDS];
+        for card in self.game.legal_cards(player) {
+            mask[card_index(&card)] = true;
+        }
+        mask
+    }
+
+    /// plays the card with index `action` for the agent , then the bots until it is the agent's turn again
+    pub fn step(&mut self, action : usize) -> Result<Step, String> {
+        if self.is_done() {
+            return Err("the episode is over , reset first".into());
+        }
+        let card = index_card(action).ok_or_else(|| format!("no card has index {action}"))?;
+        if !self.game.legal_cards(self.me).contains(&card) {
+            return Err(format!("{card} can't be played now"));
+        }
+
+        self.seeded(|env| {
+            env.play(env.me, card);
+            env.play_bots();
+        });
+
+        let done = self.is_done();
+        Ok(Step {
+            observation : self.observation(self.me),
+            reward : if done { self.reward() } else { 0.0 },
+            done,
+            truncated : self.plays >= MAX_PLAYS,
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.game.get_phase() != GamePhase::Playing || self.game.is_finished(self.me) || self.plays >= MAX_PLAYS
+    }
+
+    /// 1 for going out first down to -1 for losing , 0 when nothing was decided
+    fn reward(&self) -> f32 {
+        let last = (self.config.players - 1) as f32;
+        if let Some(place) = self.order.iter().position(|&p| p == self.me) {
+            return 1.0 - 2.0 * place as f32 / last;
+        }
+        if self.loser == Some(self.me) {
+            return -1.0;
+        }
+        0.0
+    }
+
+    fn play(&mut self, p_id : PlayerId, card : Card) {
+        self.plays += 1;
+        for ev in self.game.apply_action(Action::CardPlayedByPlayer { player_id : p_id, card, suit : None }) {
+            self.bots.iter_mut().for_each(|(_, bot)| bot.observe(&ev));
+            match ev {
+                Event::PlayerWon { player_id } => self.order.push(player_id),
+                Event::EndGame { p_id } => self.loser = Some(p_id),
+                _ => {}
+            }
+        }
+    }
+
+    /// the bots move until the agent is on turn again or out
+    fn play_bots(&mut self) {
+        let me = self.me;
+        let (order, loser) = (&mut self.order, &mut self.loser);
+        play_bots(&mut self.game, &mut self.bots, &mut self.plays, |game| game.is_finished(me), |ev| match *ev {
+            Event::PlayerWon { player_id } => order.push(player_id),
+            Event::EndGame { p_id } => *loser = Some(p_id),
+            _ => {}
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::export::{Policy, rollout};
+
+    #[test]
+    fn observation_has_the_documented_size() {
+        for players in 2..=MAX_PLAYERS {
+            let mut env = Env::new(EnvConfig { players, ..EnvConfig::default() });
+            assert_eq!(env.reset(3).len(), OBSERVATION_SIZE);
+            let action = env.action_mask(env.get_agent()).iter().position(|&legal| legal).expect("the agent has a move");
+            assert_eq!(env.step(action).expect("legal move").observation.len(), OBSERVATION_SIZE);
+        }
+    }
+
+    #[test]
+    fn every_card_has_its_own_index() {
+        for i in 0..CARDS {
+            let card = index_card(i).expect("index is in range");
+            assert_eq!(card_index(&card), i);
+        }
+        assert_eq!(index_card(CARDS), None);
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_episode() {
+        let mut env = Env::new(EnvConfig::default());
+        let mut first_legal = |_ : &Env, _ : &[f32], mask : &[bool]| mask.iter().position(|&legal| legal).unwrap_or(0);
+        let episode = |env : &mut Env, policy : &mut Policy| {
+            rollout(env, 0, 42, policy).expect("the policy plays legal cards").into_iter().map(|t| (t.observation, t.action, t.reward)).collect::<Vec<_>>()
+        };
+        let once = episode(&mut env, &mut first_legal);
+        // the thread's rng moving on in between changes nothing
+        rng::with(|rng| rng.next_u64());
+        let again = episode(&mut env, &mut first_legal);
+        assert!(!once.is_empty());
+        assert_eq!(once, again);
+    }
+
+    #[test]
+    fn the_env_leaves_the_thread_rng_alone() {
+        rng::seed(Some(5));
+        let expected = rng::with(|rng| rng.next_u64());
+        rng::seed(Some(5));
+        let mut env = Env::new(EnvConfig::default());
+        let action = env.action_mask(env.get_agent()).iter().position(|&legal| legal).expect("the agent has a move");
+        env.step(action).expect("legal move");
+        assert_eq!(rng::with(|rng| rng.next_u64()), expected);
+        rng::seed(None);
+    }
+
+    #[test]
+    fn illegal_actions_are_refused() {
+        let mut env = Env::new(EnvConfig::default());
+        let mask = env.action_mask(env.get_agent());
+        let illegal = mask.iter().position(|&legal| !legal).expect("not every card is playable");
+        assert!(env.step(illegal).is_err());
+        assert!(env.step(CARDS).is_err());
+    }
+}
@@ -1,11 +1,16 @@
-use crate::game::{card::Card, players::PlayerId};
+use crate::game::{card::{Card, Suit}, players::PlayerId, settings::RoomSettings};
+use crate::bot::profile::BotProfile;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 pub enum Action {
     // StartGame,
     // AbortGame,
-    EndGame,
+
+    // closes the post game lobby , seated players only
+    EndGame {
+        player_id : PlayerId
+    },
 
     // AddPlayer { player_id: PlayerId },
     // RemovePlayer { player_id: PlayerId },
@@ -13,10 +18,102 @@ pub enum Action {
     CardPlayedByPlayer {
         player_id: PlayerId,
         card: Card,
+        // crazy eights , the suit an eight calls
+        #[serde(default)]
+        suit: Option<Suit>,
+    },
+
+    // crazy eights , only when there is nothing to play
+    DrawCard {
+        player_id : PlayerId
     },
 
     Ready {
+        player_id : PlayerId,
+        #[serde(default)]
+        name : Option<String>,
+    },
+
+    // take back a ready while still in the lobby
+    Unready {
         player_id : PlayerId
-    }
+    },
+
+    // leave the table without closing the socket
+    LeaveTable {
+        player_id : PlayerId
+    },
+
+    // host only actions
+    UpdateSettings {
+        player_id : PlayerId,
+        settings : RoomSettings,
+    },
+
+    TransferHost {
+        player_id : PlayerId,
+        to : PlayerId,
+    },
+
+    KickPlayer {
+        player_id : PlayerId,
+        target : PlayerId,
+    },
+
+    ForceStart {
+        player_id : PlayerId
+    },
+
+    // seats a bot , how it plays is up to the host
+    AddBot {
+        player_id : PlayerId,
+        #[serde(default)]
+        profile : BotProfile,
+    },
+
+    // hearts , three cards to the player the round passes to
+    PassCards {
+        player_id : PlayerId,
+        cards : Vec<Card>,
+    },
+
+    // callbreak , tricks you expect to take
+    Bid {
+        player_id : PlayerId,
+        bid : u32,
+    },
 
-}
\ No newline at end of file
+    // only valid after a game has ended
+    Rematch {
+        player_id : PlayerId
+    },
+
+    // the legal cards ranked best first , answered privately with Hints
+    Hint {
+        player_id : PlayerId
+    },
+
+}
+
+impl Action {
+    /// the player the action claims to come from
+    pub fn player_id(&self) -> Option<PlayerId> {
+        match self {
+            Action::CardPlayedByPlayer { player_id, .. }
+            | Action::EndGame { player_id }
+            | Action::Ready { player_id, .. }
+            | Action::Unready { player_id }
+            | Action::LeaveTable { player_id }
+            | Action::UpdateSettings { player_id, .. }
+            | Action::TransferHost { player_id, .. }
+            | Action::KickPlayer { player_id, .. }
+            | Action::ForceStart { player_id }
+            | Action::AddBot { player_id, .. }
+            | Action::PassCards { player_id, .. }
+            | Action::Bid { player_id, .. }
+            | Action::DrawCard { player_id }
+            | Action::Rematch { player_id }
+            | Action::Hint { player_id } => Some(*player_id),
+        }
+    }
+}
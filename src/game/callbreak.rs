@@ -0,0 +1,358 @@
+use crate::game::card::{Card, Rank, Suit, shuffle, standard_deck};
+use crate::game::event::Event;
+use crate::game::players::PlayerId;
+use crate::game::trick::Trick;
+
+/// a game is this many deals , the dealer moves one seat on each time
+pub const ROUNDS : u32 = 5;
+pub const MIN_BID : u32 = 1;
+pub const MAX_BID : u32 = 8;
+pub const TRUMP : Suit = Suit::Spade;
+
+/// tricks a hand should be good for , sure winners plus long spades
+pub fn suggested_bid(hand : &[Card]) -> u32 {
+    let spades = hand.iter().filter(|c| c.suit == TRUMP).count() as u32;
+    let high = hand.iter().filter(|c| c.rank == Rank::Ace || (c.suit == TRUMP && c.rank >= Rank::Queen)).count() as u32;
+    (high + spades.saturating_sub(3)).clamp(MIN_BID, MAX_BID)
+}
+
+/// callbreak for four , spades are always trump and every deal is scored against the bids
+#[derive(Clone)]
+pub struct Callbreak {
+    seats : Vec<PlayerId>,
+    hands : Vec<Vec<Card>>,
+    /// deals finished so far
+    round : u32,
+    dealer : usize,
+    bidding : bool,
+    bids : Vec<Option<u32>>,
+    trick : Trick,
+    /// seat index on turn , to bid or to play
+    turn : usize,
+    /// tricks taken this deal , per seat
+    tricks : Vec<u32>,
+    /// in tenths , one extra trick over the bid is worth 0.1
+    scores : Vec<i32>,
+    over : bool,
+}
+
+impl Callbreak {
+    pub fn new(seats : Vec<PlayerId>) -> Self {
+        let n = seats.len();
+        Self {
+            seats,
+            hands : vec![Vec::new(); n],
+            round : 0,
+            dealer : 0,
+            bidding : false,
+            bids : vec![None; n],
+            trick : Trick::default(),
+            turn : 0,
+            tricks : vec![0; n],
+            scores : vec![0; n],
+            over : false,
+        }
+    }
+
+    /// shuffles and deals , bidding starts left of the dealer
+    pub fn start_round(&mut self) {
+        let n = self.seats.len();
+        let mut deck = standard_deck();
+        shuffle(&mut deck);
+
+        for hand in &mut self.hands {
+            hand.clear();
+        }
+        for (i, card) in deck.into_iter().enumerate() {
+            self.hands[(self.dealer + 1 + i) % n].push(card);
+        }
+
+        self.bidding = true;
+        self.bids = vec![None; n];
+        self.trick = Trick::new(n, Some(TRUMP));
+        self.tricks = vec![0; n];
+        self.turn = (self.dealer + 1) % n;
+    }
+
+    /// what everyone needs to know when a deal starts
+    pub fn round_events(&self) -> Vec<Event> {
+        vec![Event::NextBid { player_id : self.get_turn() }]
+    }
+
+    pub fn is_bidding(&self) -> bool {
+        self.bidding
+    }
+
+    pub fn get_turn(&self) -> PlayerId {
+        self.seats[self.turn]
+    }
+
+    pub fn get_hand(&self, p_id : PlayerId) -> Option<Vec<Card>> {
+        self.seat(p_id).map(|s| self.hands[s].clone())
+    }
+
+    pub fn set_hand(&mut self, p_id : PlayerId, cards : Vec<Card>) {
+        if let Some(s) = self.seat(p_id) {
+            self.hands[s] = cards;
+        }
+    }
+
+    pub fn get_trick(&self) -> &[(Card, PlayerId)] {
+        self.trick.plays()
+    }
+
+    /// total score per seat in seat order , positive when bids were made
+    pub fn get_scores(&self) -> Vec<f64> {
+        self.scores.iter().map(|&s| s as f64 / 10.0).collect()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// seats best first , the last one lost
+    pub fn standings(&self) -> Vec<PlayerId> {
+        let mut order : Vec<usize> = (0..self.seats.len()).collect();
+        order.sort_by_key(|&s| std::cmp::Reverse(self.scores[s]));
+        order.into_iter().map(|s| self.seats[s]).collect()
+    }
+
+    pub fn bid(&mut self, p_id : PlayerId, bid : u32) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if !self.bidding || seat != self.turn {
+            return vec![Event::InvalidPlayer];
+        }
+        if !(MIN_BID..=MAX_BID).contains(&bid) {
+            return vec![Event::Error { message : format!("bids must be between {MIN_BID} and {MAX_BID}") }];
+        }
+
+        self.bids[seat] = Some(bid);
+        let mut res = vec![Event::BidPlaced { p_id, bid }];
+
+        let n = self.seats.len();
+        self.turn = (self.turn + 1) % n;
+        if self.bids.iter().all(Option::is_some) {
+            // the player left of the dealer also leads the first trick
+            self.bidding = false;
+            self.turn = (self.dealer + 1) % n;
+            res.push(Event::NextTurn { player_id : self.get_turn() });
+        } else {
+            res.push(Event::NextBid { player_id : self.get_turn() });
+        }
+        res
+    }
+
+    /// cards `p_id` may play right now , empty if it is not their turn
+    /// follow suit and beat the trick if you can , trump when void , over trump if you can
+    pub fn legal_cards(&self, p_id : PlayerId) -> Vec<Card> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![];
+        };
+        if self.bidding || seat != self.turn {
+            return vec![];
+        }
+        let hand = &self.hands[seat];
+        let Some((best, _)) = self.trick.winner() else {
+            return hand.clone();
+        };
+
+        let beats = |c : &&Card| c.suit == best.suit && c.rank > best.rank;
+        if self.trick.can_follow(hand) {
+            let follow = self.trick.follow_cards(hand);
+            let higher : Vec<Card> = follow.iter().filter(beats).copied().collect();
+            return if higher.is_empty() { follow } else { higher };
+        }
+
+        let trumps : Vec<Card> = hand.iter().copied().filter(|c| c.suit == TRUMP).collect();
+        if best.suit != TRUMP && !trumps.is_empty() {
+            return trumps;
+        }
+        let over : Vec<Card> = trumps.iter().filter(beats).copied().collect();
+        if !over.is_empty() {
+            return over;
+        }
+        hand.clone()
+    }
+
+    pub fn play_card(&mut self, p_id : PlayerId, card : Card) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if self.bidding || seat != self.turn {
+            return vec![Event::InvalidPlayer];
+        }
+        if !self.legal_cards(p_id).contains(&card) {
+            return vec![Event::InvalidCard { p_id }];
+        }
+
+        self.hands[seat].retain(|c| *c != card);
+        self.trick.play(card, p_id);
+        let mut res = vec![Event::CardPlayed { card, p_id }];
+
+        let n = self.seats.len();
+        if !self.trick.is_complete() {
+            self.turn = (self.turn + 1) % n;
+            res.push(Event::NextTurn { player_id : self.get_turn() });
+            return res;
+        }
+
+        let (_, winner) = self.trick.winner().expect("trick is full");
+        self.trick.take();
+        let winner_seat = self.seat(winner).expect("winner is seated");
+        self.tricks[winner_seat] += 1;
+        self.turn = winner_seat;
+        res.push(Event::TrickWon { p_id : winner, points : 0 });
+
+        if self.hands.iter().any(|h| !h.is_empty()) {
+            res.push(Event::NextTurn { player_id : winner });
+            return res;
+        }
+
+        self.end_round(&mut res);
+        res
+    }
+
+    fn end_round(&mut self, res : &mut Vec<Event>) {
+        let bids : Vec<u32> = self.bids.iter().map(|b| b.unwrap_or(MIN_BID)).collect();
+
+        // making the bid scores it plus a tenth per extra trick , falling short loses it
+        let points : Vec<i32> = bids.iter().zip(&self.tricks).map(|(&bid, &won)| {
+            if won >= bid { (bid * 10 + won - bid) as i32 } else { -(bid as i32 * 10) }
+        }).collect();
+        for (score, p) in self.scores.iter_mut().zip(&points) {
+            *score += p;
+        }
+
+        self.round += 1;
+        res.push(Event::RoundResult {
+            round : self.round,
+            bids,
+            tricks : self.tricks.clone(),
+            points : points.iter().map(|&p| p as f64 / 10.0).collect(),
+            scores : self.get_scores(),
+        });
+
+        if self.round >= ROUNDS {
+            self.over = true;
+            return;
+        }
+        self.dealer = (self.dealer + 1) % self.seats.len();
+        self.start_round();
+        res.push(Event::HandsDealt);
+        res.extend(self.round_events());
+    }
+
+    fn seat(&self, p_id : PlayerId) -> Option<usize> {
+        self.seats.iter().position(|&s| s == p_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    fn table() -> Callbreak {
+        Callbreak::new((0..4).map(PlayerId).collect())
+    }
+
+    /// bidding is done , seat 0 leads with these hands
+    fn playing(hands : Vec<Vec<Card>>) -> Callbreak {
+        let mut game = table();
+        game.trick = Trick::new(4, Some(TRUMP));
+        game.hands = hands;
+        game.bids = vec![Some(MIN_BID); 4];
+        game
+    }
+
+    #[test]
+    fn bids_go_round_the_table_within_limits() {
+        let mut game = table();
+        game.start_round();
+        assert_eq!(game.get_turn(), PlayerId(1));
+
+        assert!(matches!(game.bid(PlayerId(2), 3).as_slice(), [Event::InvalidPlayer]));
+        assert!(matches!(game.bid(PlayerId(1), MAX_BID + 1).as_slice(), [Event::Error { .. }]));
+        assert!(matches!(game.bid(PlayerId(1), 0).as_slice(), [Event::Error { .. }]));
+
+        for p in [1, 2, 3] {
+            assert!(matches!(game.bid(PlayerId(p), 2).last(), Some(Event::NextBid { .. })));
+        }
+        let events = game.bid(PlayerId(0), 2);
+        assert!(matches!(events.last(), Some(Event::NextTurn { player_id }) if *player_id == PlayerId(1)));
+        assert!(!game.is_bidding());
+    }
+
+    #[test]
+    fn spades_win_over_the_led_suit() {
+        let mut game = playing(vec![
+            vec![card(Rank::Ace, Suit::Heart)],
+            vec![card(Rank::Two, Suit::Spade)],
+            vec![card(Rank::King, Suit::Heart)],
+            vec![card(Rank::Three, Suit::Heart)],
+        ]);
+        for (p, c) in [(0, card(Rank::Ace, Suit::Heart)), (1, card(Rank::Two, Suit::Spade)), (2, card(Rank::King, Suit::Heart))] {
+            game.play_card(PlayerId(p), c);
+        }
+        let events = game.play_card(PlayerId(3), card(Rank::Three, Suit::Heart));
+        assert!(events.iter().any(|ev| matches!(ev, Event::TrickWon { p_id, .. } if *p_id == PlayerId(1))));
+    }
+
+    #[test]
+    fn followers_must_beat_the_trick_and_trump_when_void() {
+        let mut game = playing(vec![
+            vec![card(Rank::Ten, Suit::Heart)],
+            vec![card(Rank::Four, Suit::Heart), card(Rank::Queen, Suit::Heart)],
+            vec![card(Rank::Five, Suit::Spade), card(Rank::Ace, Suit::Club)],
+            vec![card(Rank::Three, Suit::Spade), card(Rank::Nine, Suit::Spade), card(Rank::Two, Suit::Diamond)],
+        ]);
+        game.play_card(PlayerId(0), card(Rank::Ten, Suit::Heart));
+        assert_eq!(game.legal_cards(PlayerId(1)), vec![card(Rank::Queen, Suit::Heart)]);
+
+        game.play_card(PlayerId(1), card(Rank::Queen, Suit::Heart));
+        assert_eq!(game.legal_cards(PlayerId(2)), vec![card(Rank::Five, Suit::Spade)]);
+
+        // a spade is already winning , only a higher one will do
+        game.play_card(PlayerId(2), card(Rank::Five, Suit::Spade));
+        assert_eq!(game.legal_cards(PlayerId(3)), vec![card(Rank::Nine, Suit::Spade)]);
+    }
+
+    #[test]
+    fn a_trump_that_cant_overtrump_is_not_forced() {
+        let mut game = playing(vec![
+            vec![card(Rank::Ten, Suit::Heart)],
+            vec![card(Rank::King, Suit::Spade)],
+            vec![card(Rank::Five, Suit::Spade), card(Rank::Ace, Suit::Club)],
+            vec![card(Rank::Two, Suit::Heart)],
+        ]);
+        game.play_card(PlayerId(0), card(Rank::Ten, Suit::Heart));
+        game.play_card(PlayerId(1), card(Rank::King, Suit::Spade));
+        assert_eq!(game.legal_cards(PlayerId(2)).len(), 2);
+    }
+
+    #[test]
+    fn made_bids_score_with_a_tenth_per_extra_trick() {
+        let mut game = table();
+        game.bids = vec![Some(3), Some(4), Some(2), Some(1)];
+        game.tricks = vec![3, 2, 5, 3];
+        let mut events = Vec::new();
+        game.end_round(&mut events);
+
+        assert_eq!(game.get_scores(), vec![3.0, -4.0, 2.3, 1.2]);
+        assert!(matches!(events.first(), Some(Event::RoundResult { round : 1, .. })));
+    }
+
+    #[test]
+    fn the_game_is_over_after_the_last_deal() {
+        let mut game = table();
+        game.round = ROUNDS - 1;
+        game.bids = vec![Some(MIN_BID); 4];
+        game.end_round(&mut Vec::new());
+        assert!(game.is_over());
+    }
+}
@@ -1,4 +1,5 @@
 use rand::Rng;
+use crate::game::rng;
 use serde::{Serialize, Deserialize};
 #[derive(
     Debug,
@@ -59,6 +60,65 @@ pub struct Card{
     pub suit : Suit,
 }
 
+/// short form like `AS` , `TD` or `7C` , rank first then suit
+impl std::fmt::Display for Card {
+    fn fmt(&self, f : &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rank = match self.rank {
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+            rank => char::from_digit(rank as u32, 10).expect("single digit rank"),
+        };
+        let suit = match self.suit {
+            Suit::Spade => 'S',
+            Suit::Heart => 'H',
+            Suit::Diamond => 'D',
+            Suit::Club => 'C',
+        };
+        write!(f, "{rank}{suit}")
+    }
+}
+
+/// reads the short form back , `10` works as well as `T` and case doesn't matter
+impl std::str::FromStr for Card {
+    type Err = String;
+
+    fn from_str(s : &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_uppercase();
+        let bad = || format!("not a card: {s}");
+        if !s.is_ascii() {
+            return Err(bad());
+        }
+        let (rank, suit) = s.split_at(s.len().checked_sub(1).ok_or_else(bad)?);
+        let rank = match rank {
+            "2" => Rank::Two,
+            "3" => Rank::Three,
+            "4" => Rank::Four,
+            "5" => Rank::Five,
+            "6" => Rank::Six,
+            "7" => Rank::Seven,
+            "8" => Rank::Eight,
+            "9" => Rank::Nine,
+            "T" | "10" => Rank::Ten,
+            "J" => Rank::Jack,
+            "Q" => Rank::Queen,
+            "K" => Rank::King,
+            "A" => Rank::Ace,
+            _ => return Err(bad()),
+        };
+        let suit = match suit {
+            "S" => Suit::Spade,
+            "H" => Suit::Heart,
+            "D" => Suit::Diamond,
+            "C" => Suit::Club,
+            _ => return Err(bad()),
+        };
+        Ok(Card { rank, suit })
+    }
+}
+
 // impl Card{
 //     pub fn is_smaller(&self , other : &Card) -> bool{
 //         self.rank < other.rank
@@ -102,12 +162,42 @@ pub fn standard_deck() -> Vec<Card> {
 
     deck
 }
-pub fn shuffle(cards: &mut Vec<Card>) {
-    let mut rng = rand::rng();
+pub fn shuffle(cards: &mut [Card]) {
     let n = cards.len();
 
-    for i in (1..n).rev() {
-        let j = rng.random_range(0..=i);
-        cards.swap(i, j);
+    rng::with(|rng| {
+        for i in (1..n).rev() {
+            let j = rng.random_range(0..=i);
+            cards.swap(i, j);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_card_reads_back_what_it_prints() {
+        for card in standard_deck() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn short_forms() {
+        let ten = Card { rank : Rank::Ten, suit : Suit::Heart };
+        assert_eq!(ten.to_string(), "TH");
+        assert_eq!("TH".parse(), Ok(ten));
+        assert_eq!("10H".parse(), Ok(ten));
+        assert_eq!(" th ".parse(), Ok(ten));
+        assert_eq!(Card { rank : Rank::Seven, suit : Suit::Club }.to_string(), "7C");
+    }
+
+    #[test]
+    fn bad_cards_are_refused() {
+        for bad in ["", "A", "1H", "11S", "AX", "SA", "A♠", "QHH"] {
+            assert!(bad.parse::<Card>().is_err(), "{bad} parsed");
+        }
     }
 }
@@ -0,0 +1,307 @@
+use crate::game::card::{Card, Rank, Suit, shuffle, standard_deck};
+use crate::game::event::Event;
+use crate::game::pile::DrawPile;
+use crate::game::players::PlayerId;
+
+/// cards dealt to each player , two players get more
+pub fn hand_size(players : usize) -> usize {
+    if players == 2 { 7 } else { 5 }
+}
+
+/// what a card left in hand costs at the end
+pub fn penalty(card : &Card) -> u32 {
+    match card.rank {
+        Rank::Eight => 50,
+        Rank::Jack | Rank::Queen | Rank::King => 10,
+        Rank::Ace => 1,
+        rank => rank as u32,
+    }
+}
+
+/// the suit an eight should call , whatever the hand holds most of
+pub fn suggested_suit(hand : &[Card], fallback : Suit) -> Suit {
+    [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club]
+        .into_iter()
+        .max_by_key(|&s| hand.iter().filter(|c| c.suit == s && c.rank != Rank::Eight).count())
+        .filter(|&s| hand.iter().any(|c| c.suit == s && c.rank != Rank::Eight))
+        .unwrap_or(fallback)
+}
+
+/// match the top card by suit or rank , eights are wild and call a new suit , first one out wins
+#[derive(Clone)]
+pub struct CrazyEights {
+    seats : Vec<PlayerId>,
+    hands : Vec<Vec<Card>>,
+    pile : DrawPile,
+    /// suit to follow , the called one after an eight
+    suit : Suit,
+    turn : usize,
+    /// turns in a row where nobody could play or draw
+    passes : usize,
+    over : bool,
+}
+
+impl CrazyEights {
+    /// deals and turns up the first card , an eight there goes back into the stock
+    pub fn new(seats : Vec<PlayerId>) -> Self {
+        let n = seats.len();
+        let mut deck = standard_deck();
+        shuffle(&mut deck);
+
+        let mut hands = vec![Vec::new(); n];
+        for i in 0..hand_size(n) * n {
+            hands[i % n].push(deck.pop().expect("deck has enough cards"));
+        }
+
+        let mut pile = DrawPile::new(deck);
+        let first = loop {
+            let (card, _) = pile.draw();
+            let card = card.expect("stock has cards");
+            if card.rank != Rank::Eight {
+                break card;
+            }
+            pile.bury(card);
+        };
+        pile.discard(first);
+
+        Self { seats, hands, pile, suit : first.suit, turn : 0, passes : 0, over : false }
+    }
+
+    /// what everyone needs to know when the game starts
+    pub fn round_events(&self) -> Vec<Event> {
+        vec![
+            Event::TopCard { card : self.top(), suit : self.suit },
+            Event::NextTurn { player_id : self.get_turn() },
+        ]
+    }
+
+    pub fn get_turn(&self) -> PlayerId {
+        self.seats[self.turn]
+    }
+
+    pub fn get_hand(&self, p_id : PlayerId) -> Option<Vec<Card>> {
+        self.seat(p_id).map(|s| self.hands[s].clone())
+    }
+
+    pub fn top(&self) -> Card {
+        self.pile.top().expect("the discard pile is never empty")
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// cards left in hand are penalty points , per seat
+    pub fn get_scores(&self) -> Vec<f64> {
+        self.hands.iter().map(|h| h.iter().map(penalty).sum::<u32>() as f64).collect()
+    }
+
+    /// seats best first , whoever went out leads , the rest by penalty points
+    pub fn standings(&self) -> Vec<PlayerId> {
+        let scores = self.get_scores();
+        let mut order : Vec<usize> = (0..self.seats.len()).collect();
+        order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+        order.into_iter().map(|s| self.seats[s]).collect()
+    }
+
+    /// cards `p_id` may play right now , empty if it is not their turn or they have to draw
+    pub fn legal_cards(&self, p_id : PlayerId) -> Vec<Card> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![];
+        };
+        if self.over || seat != self.turn {
+            return vec![];
+        }
+        let top = self.top();
+        self.hands[seat].iter().copied().filter(|c| c.rank == Rank::Eight || c.suit == self.suit || c.rank == top.rank).collect()
+    }
+
+    /// true when `p_id` is on turn with nothing to play
+    pub fn must_draw(&self, p_id : PlayerId) -> bool {
+        self.seat(p_id) == Some(self.turn) && !self.over && self.legal_cards(p_id).is_empty()
+    }
+
+    /// draws one card , a playable one can be played straight away , otherwise the turn moves on
+    pub fn draw(&mut self, p_id : PlayerId) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if self.over || seat != self.turn {
+            return vec![Event::InvalidPlayer];
+        }
+        if !self.legal_cards(p_id).is_empty() {
+            return vec![Event::Error { message : "you have a card to play".into() }];
+        }
+
+        let mut res = Vec::new();
+        let (card, reshuffled) = self.pile.draw();
+        if reshuffled {
+            res.push(Event::StockReshuffled);
+        }
+
+        let Some(card) = card else {
+            // nothing left anywhere , once everyone is stuck the game is blocked
+            res.push(Event::TurnPassed { p_id });
+            self.passes += 1;
+            if self.passes >= self.seats.len() {
+                self.over = true;
+                return res;
+            }
+            self.next_turn(&mut res);
+            return res;
+        };
+
+        self.passes = 0;
+        self.hands[seat].push(card);
+        res.push(Event::CardDrawn { p_id });
+        if self.legal_cards(p_id).contains(&card) {
+            res.push(Event::NextTurn { player_id : p_id });
+        } else {
+            self.next_turn(&mut res);
+        }
+        res
+    }
+
+    /// plays a card , an eight calls `suit` or the suit the hand holds most of
+    pub fn play_card(&mut self, p_id : PlayerId, card : Card, suit : Option<Suit>) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if self.over || seat != self.turn {
+            return vec![Event::InvalidPlayer];
+        }
+        if !self.legal_cards(p_id).contains(&card) {
+            return vec![Event::InvalidCard { p_id }];
+        }
+
+        self.hands[seat].retain(|c| *c != card);
+        self.pile.discard(card);
+        self.passes = 0;
+        let mut res = vec![Event::CardPlayed { card, p_id }];
+
+        if card.rank == Rank::Eight {
+            self.suit = suit.unwrap_or_else(|| suggested_suit(&self.hands[seat], card.suit));
+            res.push(Event::SuitNominated { p_id, suit : self.suit });
+        } else {
+            self.suit = card.suit;
+        }
+
+        if self.hands[seat].is_empty() {
+            self.over = true;
+            res.push(Event::PlayerWon { player_id : p_id });
+            return res;
+        }
+        self.next_turn(&mut res);
+        res
+    }
+
+    fn next_turn(&mut self, res : &mut Vec<Event>) {
+        self.turn = (self.turn + 1) % self.seats.len();
+        res.push(Event::NextTurn { player_id : self.get_turn() });
+    }
+
+    fn seat(&self, p_id : PlayerId) -> Option<usize> {
+        self.seats.iter().position(|&s| s == p_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    /// two seats with these hands and stock , `top` turned up , seat 0 on turn
+    fn table(hands : Vec<Vec<Card>>, stock : Vec<Card>, top : Card) -> CrazyEights {
+        let mut pile = DrawPile::new(stock);
+        pile.discard(top);
+        CrazyEights { seats : vec![PlayerId(0), PlayerId(1)], hands, pile, suit : top.suit, turn : 0, passes : 0, over : false }
+    }
+
+    #[test]
+    fn match_by_suit_or_rank_and_eights_are_wild() {
+        let game = table(vec![
+            vec![card(Rank::Four, Suit::Club), card(Rank::Nine, Suit::Heart), card(Rank::Two, Suit::Diamond), card(Rank::Eight, Suit::Spade)],
+            vec![card(Rank::Ace, Suit::Spade)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+        assert_eq!(game.legal_cards(PlayerId(0)), vec![card(Rank::Four, Suit::Club), card(Rank::Nine, Suit::Heart), card(Rank::Eight, Suit::Spade)]);
+        assert!(game.legal_cards(PlayerId(1)).is_empty());
+    }
+
+    #[test]
+    fn an_eight_calls_the_suit_to_follow() {
+        let mut game = table(vec![
+            vec![card(Rank::Eight, Suit::Spade), card(Rank::Two, Suit::Heart)],
+            vec![card(Rank::Three, Suit::Diamond), card(Rank::Three, Suit::Club)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+
+        let events = game.play_card(PlayerId(0), card(Rank::Eight, Suit::Spade), Some(Suit::Diamond));
+        assert!(events.iter().any(|ev| matches!(ev, Event::SuitNominated { suit : Suit::Diamond, .. })));
+        assert_eq!(game.legal_cards(PlayerId(1)), vec![card(Rank::Three, Suit::Diamond)]);
+    }
+
+    #[test]
+    fn an_eight_without_a_call_takes_the_longest_suit() {
+        let mut game = table(vec![
+            vec![card(Rank::Eight, Suit::Spade), card(Rank::Two, Suit::Heart), card(Rank::Five, Suit::Heart), card(Rank::Six, Suit::Club)],
+            vec![card(Rank::Three, Suit::Diamond)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+
+        game.play_card(PlayerId(0), card(Rank::Eight, Suit::Spade), None);
+        assert_eq!(game.suit, Suit::Heart);
+    }
+
+    #[test]
+    fn drawing_reshuffles_the_discards_into_the_stock() {
+        let mut game = table(vec![
+            vec![card(Rank::Two, Suit::Heart)],
+            vec![card(Rank::Three, Suit::Diamond)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+        game.pile.discard(card(Rank::King, Suit::Club));
+
+        let events = game.draw(PlayerId(0));
+        assert!(matches!(events.first(), Some(Event::StockReshuffled)));
+        assert!(events.iter().any(|ev| matches!(ev, Event::CardDrawn { p_id } if *p_id == PlayerId(0))));
+        assert!(game.hands[0].contains(&card(Rank::Nine, Suit::Club)));
+        assert_eq!(game.top(), card(Rank::King, Suit::Club));
+    }
+
+    #[test]
+    fn drawing_with_a_card_to_play_is_refused() {
+        let mut game = table(vec![
+            vec![card(Rank::Two, Suit::Club)],
+            vec![card(Rank::Three, Suit::Diamond)],
+        ], vec![card(Rank::Ace, Suit::Heart)], card(Rank::Nine, Suit::Club));
+        assert!(matches!(game.draw(PlayerId(0)).as_slice(), [Event::Error { .. }]));
+    }
+
+    #[test]
+    fn the_game_is_blocked_once_everyone_passes() {
+        let mut game = table(vec![
+            vec![card(Rank::Two, Suit::Heart)],
+            vec![card(Rank::Three, Suit::Diamond)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+
+        let events = game.draw(PlayerId(0));
+        assert!(matches!(events.as_slice(), [Event::TurnPassed { p_id }, Event::NextTurn { .. }] if *p_id == PlayerId(0)));
+        assert!(!game.is_over());
+
+        let events = game.draw(PlayerId(1));
+        assert!(matches!(events.as_slice(), [Event::TurnPassed { p_id }] if *p_id == PlayerId(1)));
+        assert!(game.is_over());
+        assert_eq!(game.standings(), vec![PlayerId(0), PlayerId(1)]);
+    }
+
+    #[test]
+    fn going_out_wins() {
+        let mut game = table(vec![
+            vec![card(Rank::Two, Suit::Club)],
+            vec![card(Rank::Three, Suit::Diamond)],
+        ], Vec::new(), card(Rank::Nine, Suit::Club));
+        let events = game.play_card(PlayerId(0), card(Rank::Two, Suit::Club), None);
+        assert!(matches!(events.last(), Some(Event::PlayerWon { player_id }) if *player_id == PlayerId(0)));
+        assert!(game.is_over());
+    }
+}
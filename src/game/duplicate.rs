@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::game::card::{Card, shuffle, standard_deck};
+use crate::game::settings::{MAX_PLAYERS, MIN_PLAYERS};
+
+/// one table of a duplicate set , seat `s` holds hand `(s + n - rotation) % n`
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateTable {
+    pub rotation : usize,
+    /// names in seat order once the game finished
+    pub seats : Vec<String>,
+    /// finishing place of every seat , 0 went out first , None while the game is running
+    pub result : Option<Vec<usize>>,
+}
+
+/// one seat of one table , names can repeat so the seat is what counts
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateScore {
+    pub table : usize,
+    pub seat : usize,
+    pub name : String,
+    /// places better than the others who held the same hand , on average
+    pub score : f64,
+}
+
+/// the same deal played at `table_size` tables , each with the hands moved one seat on
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSet {
+    pub table_size : usize,
+    pub deck : Vec<Card>,
+    pub tables : Vec<DuplicateTable>,
+}
+
+impl DuplicateSet {
+    pub fn new(table_size : usize) -> Result<Self, String> {
+        let mut deck = standard_deck();
+        shuffle(&mut deck);
+        Self::with_deck(table_size, deck)
+    }
+
+    pub fn with_deck(table_size : usize, deck : Vec<Card>) -> Result<Self, String> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&table_size) {
+            return Err(format!("table size must be between {MIN_PLAYERS} and {MAX_PLAYERS}"));
+        }
+
+        let tables = (0..table_size)
+            .map(|rotation| DuplicateTable { rotation, seats : Vec::new(), result : None })
+            .collect();
+        Ok(Self { table_size, deck, tables })
+    }
+
+    /// stores a finished table , `places` in seat order , ignored if the table already has a result
+    pub fn record_result(&mut self, table : usize, seats : Vec<String>, places : Vec<usize>) {
+        if let Some(t) = self.tables.get_mut(table)
+            && t.result.is_none() {
+            t.seats = seats;
+            t.result = Some(places);
+        }
+    }
+
+    /// which hand of the deal a seat held at a table
+    pub fn hand_of(&self, table : usize, seat : usize) -> usize {
+        let n = self.table_size;
+        (seat + n - self.tables[table].rotation % n) % n
+    }
+
+    /// compares every seat only with the seats at other tables who held the same cards
+    pub fn scores(&self) -> Vec<DuplicateScore> {
+        let mut by_hand : HashMap<usize, Vec<f64>> = HashMap::new();
+        for (t, table) in self.tables.iter().enumerate() {
+            for (seat, &place) in table.result.iter().flatten().enumerate() {
+                by_hand.entry(self.hand_of(t, seat)).or_default().push(place as f64);
+            }
+        }
+
+        let mut scores = Vec::new();
+        for (t, table) in self.tables.iter().enumerate() {
+            let Some(places) = &table.result else {
+                continue;
+            };
+            for (seat, &place) in places.iter().enumerate() {
+                let place = place as f64;
+                let field = &by_hand[&self.hand_of(t, seat)];
+                // a hand nobody else played yet says nothing
+                let score = if field.len() > 1 {
+                    let others = (field.iter().sum::<f64>() - place) / (field.len() - 1) as f64;
+                    others - place
+                } else {
+                    0.0
+                };
+                let name = table.seats.get(seat).cloned().unwrap_or_default();
+                scores.push(DuplicateScore { table : t, seat, name, score });
+            }
+        }
+
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names : &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn score(scores : &[DuplicateScore], table : usize, seat : usize) -> f64 {
+        scores.iter().find(|s| s.table == table && s.seat == seat).expect("seat was scored").score
+    }
+
+    #[test]
+    fn hands_move_one_seat_per_table() {
+        let set = DuplicateSet::with_deck(3, standard_deck()).unwrap();
+        assert_eq!((0..3).map(|seat| set.hand_of(0, seat)).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!((0..3).map(|seat| set.hand_of(1, seat)).collect::<Vec<_>>(), vec![2, 0, 1]);
+        assert_eq!((0..3).map(|seat| set.hand_of(2, seat)).collect::<Vec<_>>(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn seats_are_scored_against_the_same_hand() {
+        let mut set = DuplicateSet::with_deck(2, standard_deck()).unwrap();
+        // the same default names at both tables are still different people
+        set.record_result(0, names(&["Player 0", "Player 1"]), vec![0, 1]);
+        assert!(set.scores().iter().all(|s| s.score == 0.0));
+
+        set.record_result(1, names(&["Player 0", "Player 1"]), vec![0, 1]);
+        let scores = set.scores();
+        assert_eq!(scores.len(), 4);
+        // hand 0 went out first at table 0 but last at table 1
+        assert_eq!(score(&scores, 0, 0), 1.0);
+        assert_eq!(score(&scores, 1, 1), -1.0);
+        assert_eq!(score(&scores, 1, 0), 1.0);
+        assert_eq!(score(&scores, 0, 1), -1.0);
+    }
+
+    #[test]
+    fn a_table_counts_once() {
+        let mut set = DuplicateSet::with_deck(2, standard_deck()).unwrap();
+        set.record_result(0, names(&["a", "b"]), vec![1, 0]);
+        set.record_result(0, names(&["c", "d"]), vec![0, 1]);
+        assert_eq!(set.tables[0].seats, names(&["a", "b"]));
+        assert_eq!(set.tables[0].result, Some(vec![1, 0]));
+    }
+}
@@ -1,7 +1,13 @@
-use crate::game::card::Card;
+use crate::game::card::{Card, Suit};
 use crate::game::players::PlayerId;
+use crate::game::summary::GameSummary;
+use crate::game::settings::RoomSettings;
+use crate::game::hearts::PassDirection;
+use crate::bot::profile::Difficulty;
+use crate::bot::hint::Hint;
 use serde::Serialize;
 #[derive(Clone, Serialize)]
+#[allow(clippy::enum_variant_names)]
 pub enum Event {
     StartGame,
 
@@ -9,6 +15,8 @@ pub enum Event {
 
     EndGame{p_id : PlayerId},
 
+    GameOver {summary : GameSummary},
+
     CardPlayed{card : Card , p_id : PlayerId},
 
     NextTurn {
@@ -32,6 +40,12 @@ pub enum Event {
         player_id: PlayerId,
     },
 
+    // sent after SeatOrder in team games
+    Teams {teams : Vec<Vec<PlayerId>>},
+
+    // both partners are out , ends a team game
+    TeamSafe {team : usize , players : Vec<PlayerId>},
+
     // PlayerRemoved{
     //     p_id : PlayerId,
     // },
@@ -52,13 +66,90 @@ pub enum Event {
     Error {
         message: String,
     },
-    MarkReady {p_id : PlayerId},
+    MarkReady {p_id : PlayerId , name : String},
+
+    MarkUnready {p_id : PlayerId},
+
+    // bots has the difficulty of every bot seat , None for people
+    SeatOrder {seats : Vec<PlayerId> , names : Vec<String> , bots : Vec<Option<Difficulty>>},
+
+    RematchVote {p_id : PlayerId},
+
+    HostChanged {p_id : PlayerId},
+
+    SettingsChanged {settings : RoomSettings},
+
+    // the kicked socket is closed right after
+    PlayerKicked {p_id : PlayerId},
+
+    // server played for a player who ran out of time
+    TurnTimedOut {p_id : PlayerId},
+
+    // everyone voted , StartGame follows
+    Rematch,
+
+    // post game lobby closed , players have to ready up again
+    ReturnToLobby,
+
+    // hearts , everyone picks three cards to pass
+    PassingStarted {direction : PassDirection},
+
+    CardsPassed {p_id : PlayerId},
+
+    // hands changed for everyone , each player gets their new Hand privately
+    HandsDealt,
+
+    HeartsBroken,
+
+    // points taken with the trick , always 0 in callbreak
+    TrickWon {p_id : PlayerId , points : u32},
+
+    ShotTheMoon {p_id : PlayerId},
+
+    // end of a hearts round , both in seat order
+    RoundScores {round : u32 , points : Vec<u32> , scores : Vec<u32>},
+
+    // callbreak , whose bid it is
+    NextBid {player_id : PlayerId},
+
+    BidPlaced {p_id : PlayerId , bid : u32},
+
+    // end of a callbreak deal , everything in seat order
+    RoundResult {round : u32 , bids : Vec<u32> , tricks : Vec<u32> , points : Vec<f64> , scores : Vec<f64>},
+
+    // crazy eights , the card to match and the suit in play
+    TopCard {card : Card , suit : Suit},
+
+    // an eight was played , this suit must be followed now
+    SuitNominated {p_id : PlayerId , suit : Suit},
+
+    // only the drawing player gets to see the card , in a fresh Hand
+    CardDrawn {p_id : PlayerId},
+
+    // stock ran out , the discard pile under the top card was shuffled into it
+    StockReshuffled,
+
+    // nothing to play and nothing left to draw
+    TurnPassed {p_id : PlayerId},
+
+    // practice games , what the scenario shows
+    TutorialStarted {title : String , intro : String},
+
+    // the move the learner is asked for , step counts from 0
+    TutorialStep {step : usize , steps : usize , prompt : String},
+
+    // not the card asked for , nothing was played
+    TutorialMistake {p_id : PlayerId , card : Card , message : String},
 
-    SeatOrder {seats : Vec<PlayerId>}
+    TutorialDone {message : String},
 
 }
 #[derive(Serialize)]
 pub enum PrivateMsg {
     Hand {cards : Vec<Card>},
-    Id {p_id : PlayerId}
+    Id {p_id : PlayerId},
+    // sent on join so late comers know who runs the table
+    Room {room_id : u32 , code : String , host : Option<PlayerId> , settings : RoomSettings},
+    // answer to a Hint action , best card first
+    Hints {hints : Vec<Hint>},
 }
@@ -0,0 +1,435 @@
+use serde::Serialize;
+use crate::game::card::{Card, Rank, Suit, shuffle, standard_deck};
+use crate::game::event::Event;
+use crate::game::players::PlayerId;
+use crate::game::trick::Trick;
+
+/// the game ends after the round in which someone reaches this
+pub const TARGET_SCORE : u32 = 100;
+pub const PASS_COUNT : usize = 3;
+/// hearts plus the queen of spades
+const ALL_POINTS : u32 = 26;
+
+const TWO_OF_CLUBS : Card = Card { rank : Rank::Two, suit : Suit::Club };
+const QUEEN_OF_SPADES : Card = Card { rank : Rank::Queen, suit : Suit::Spade };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PassDirection {
+    Left,
+    Right,
+    Across,
+    Hold,
+}
+
+pub fn points(card : &Card) -> u32 {
+    match card {
+        c if c.suit == Suit::Heart => 1,
+        c if *c == QUEEN_OF_SPADES => 13,
+        _ => 0,
+    }
+}
+
+/// three cards worth getting rid of , the queen of spades first and then the highest
+pub fn suggested_pass(hand : &[Card]) -> Vec<Card> {
+    let mut cards = hand.to_vec();
+    cards.sort_by_key(|c| std::cmp::Reverse((*c == QUEEN_OF_SPADES, c.rank)));
+    cards.truncate(PASS_COUNT);
+    cards
+}
+
+/// a game of hearts over several rounds , lowest score wins once someone reaches `TARGET_SCORE`
+#[derive(Clone)]
+pub struct Hearts {
+    seats : Vec<PlayerId>,
+    hands : Vec<Vec<Card>>,
+    /// rounds finished so far , decides the pass direction
+    round : u32,
+    passing : bool,
+    passes : Vec<Option<Vec<Card>>>,
+    trick : Trick,
+    /// seat index on turn
+    turn : usize,
+    hearts_broken : bool,
+    tricks_played : u32,
+    /// points taken this round , per seat
+    taken : Vec<u32>,
+    scores : Vec<u32>,
+    over : bool,
+}
+
+impl Hearts {
+    pub fn new(seats : Vec<PlayerId>) -> Self {
+        let n = seats.len();
+        Self {
+            seats,
+            hands : vec![Vec::new(); n],
+            round : 0,
+            passing : false,
+            passes : vec![None; n],
+            trick : Trick::default(),
+            turn : 0,
+            hearts_broken : false,
+            tricks_played : 0,
+            taken : vec![0; n],
+            scores : vec![0; n],
+            over : false,
+        }
+    }
+
+    /// shuffles and deals a new round , three players play without the two of diamonds
+    pub fn start_round(&mut self) {
+        let n = self.seats.len();
+        let mut deck = standard_deck();
+        if n == 3 {
+            deck.retain(|c| !(c.rank == Rank::Two && c.suit == Suit::Diamond));
+        }
+        shuffle(&mut deck);
+
+        for hand in &mut self.hands {
+            hand.clear();
+        }
+        for (i, card) in deck.into_iter().enumerate() {
+            self.hands[i % n].push(card);
+        }
+
+        self.passes = vec![None; n];
+        self.trick = Trick::new(n, None);
+        self.hearts_broken = false;
+        self.tricks_played = 0;
+        self.taken = vec![0; n];
+        self.passing = self.direction() != PassDirection::Hold;
+        if !self.passing {
+            self.turn = self.two_of_clubs_holder();
+        }
+    }
+
+    /// what everyone needs to know when a round starts
+    pub fn round_events(&self) -> Vec<Event> {
+        if self.passing {
+            vec![Event::PassingStarted { direction : self.direction() }]
+        } else {
+            vec![Event::NextTurn { player_id : self.seats[self.turn] }]
+        }
+    }
+
+    pub fn direction(&self) -> PassDirection {
+        let cycle : &[PassDirection] = if self.seats.len() == 4 {
+            &[PassDirection::Left, PassDirection::Right, PassDirection::Across, PassDirection::Hold]
+        } else {
+            &[PassDirection::Left, PassDirection::Right, PassDirection::Hold]
+        };
+        cycle[self.round as usize % cycle.len()]
+    }
+
+    pub fn is_passing(&self) -> bool {
+        self.passing
+    }
+
+    pub fn has_passed(&self, p_id : PlayerId) -> bool {
+        self.seat(p_id).is_some_and(|s| self.passes[s].is_some())
+    }
+
+    pub fn get_turn(&self) -> PlayerId {
+        self.seats[self.turn]
+    }
+
+    pub fn get_hand(&self, p_id : PlayerId) -> Option<Vec<Card>> {
+        self.seat(p_id).map(|s| self.hands[s].clone())
+    }
+
+    pub fn set_hand(&mut self, p_id : PlayerId, cards : Vec<Card>) {
+        if let Some(s) = self.seat(p_id) {
+            self.hands[s] = cards;
+        }
+    }
+
+    pub fn get_trick(&self) -> &[(Card, PlayerId)] {
+        self.trick.plays()
+    }
+
+    /// total score per seat , in seat order
+    pub fn get_scores(&self) -> &[u32] {
+        &self.scores
+    }
+
+    /// over once a round ended with someone at or above the target
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// seats best first , the last one lost
+    pub fn standings(&self) -> Vec<PlayerId> {
+        let mut order : Vec<usize> = (0..self.seats.len()).collect();
+        order.sort_by_key(|&s| self.scores[s]);
+        order.into_iter().map(|s| self.seats[s]).collect()
+    }
+
+    /// hands over three cards , once everyone has passed the cards move and play starts
+    pub fn pass_cards(&mut self, p_id : PlayerId, cards : Vec<Card>) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if !self.passing || self.passes[seat].is_some() {
+            return vec![Event::InvalidPlayer];
+        }
+
+        let distinct = cards.iter().enumerate().all(|(i, c)| !cards[..i].contains(c));
+        if cards.len() != PASS_COUNT || !distinct || !cards.iter().all(|c| self.hands[seat].contains(c)) {
+            return vec![Event::InvalidCard { p_id }];
+        }
+
+        self.hands[seat].retain(|c| !cards.contains(c));
+        self.passes[seat] = Some(cards);
+        let mut res = vec![Event::CardsPassed { p_id }];
+
+        if self.passes.iter().all(Option::is_some) {
+            let n = self.seats.len();
+            let offset = match self.direction() {
+                PassDirection::Left => 1,
+                PassDirection::Right => n - 1,
+                PassDirection::Across => 2,
+                PassDirection::Hold => 0,
+            };
+            for from in 0..n {
+                let cards = self.passes[from].clone().unwrap_or_default();
+                self.hands[(from + offset) % n].extend(cards);
+            }
+
+            self.passing = false;
+            self.turn = self.two_of_clubs_holder();
+            res.push(Event::HandsDealt);
+            res.push(Event::NextTurn { player_id : self.get_turn() });
+        }
+        res
+    }
+
+    /// cards `p_id` may play right now , empty if it is not their turn
+    pub fn legal_cards(&self, p_id : PlayerId) -> Vec<Card> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![];
+        };
+        if self.passing || seat != self.turn {
+            return vec![];
+        }
+        let hand = &self.hands[seat];
+        let first_trick = self.tricks_played == 0;
+
+        if self.trick.is_empty() {
+            if first_trick {
+                return hand.iter().copied().filter(|c| *c == TWO_OF_CLUBS).collect();
+            }
+            // hearts can only be led once broken , unless nothing else is left
+            let no_hearts : Vec<Card> = hand.iter().copied().filter(|c| c.suit != Suit::Heart).collect();
+            return if self.hearts_broken || no_hearts.is_empty() { hand.clone() } else { no_hearts };
+        }
+
+        if self.trick.can_follow(hand) {
+            return self.trick.follow_cards(hand);
+        }
+        // no points on the first trick , unless the hand is nothing but points
+        if first_trick {
+            let clean : Vec<Card> = hand.iter().copied().filter(|c| points(c) == 0).collect();
+            if !clean.is_empty() {
+                return clean;
+            }
+        }
+        hand.clone()
+    }
+
+    pub fn play_card(&mut self, p_id : PlayerId, card : Card) -> Vec<Event> {
+        let Some(seat) = self.seat(p_id) else {
+            return vec![Event::InvalidPlayer];
+        };
+        if self.passing || seat != self.turn {
+            return vec![Event::InvalidPlayer];
+        }
+        if !self.legal_cards(p_id).contains(&card) {
+            return vec![Event::InvalidCard { p_id }];
+        }
+
+        self.hands[seat].retain(|c| *c != card);
+        self.trick.play(card, p_id);
+        let mut res = vec![Event::CardPlayed { card, p_id }];
+
+        if card.suit == Suit::Heart && !self.hearts_broken {
+            self.hearts_broken = true;
+            res.push(Event::HeartsBroken);
+        }
+
+        let n = self.seats.len();
+        if !self.trick.is_complete() {
+            self.turn = (self.turn + 1) % n;
+            res.push(Event::NextTurn { player_id : self.get_turn() });
+            return res;
+        }
+
+        // no trumps , highest card of the led suit takes the trick
+        let (_, winner) = self.trick.winner().expect("trick is full");
+        let won = self.trick.take().iter().map(|(c, _)| points(c)).sum();
+        let winner_seat = self.seat(winner).expect("winner is seated");
+        self.taken[winner_seat] += won;
+        self.tricks_played += 1;
+        self.turn = winner_seat;
+        res.push(Event::TrickWon { p_id : winner, points : won });
+
+        if self.hands.iter().any(|h| !h.is_empty()) {
+            res.push(Event::NextTurn { player_id : winner });
+            return res;
+        }
+
+        self.end_round(&mut res);
+        res
+    }
+
+    fn end_round(&mut self, res : &mut Vec<Event>) {
+        // taking every point hands them to everyone else instead
+        if let Some(moon) = self.taken.iter().position(|&t| t == ALL_POINTS) {
+            res.push(Event::ShotTheMoon { p_id : self.seats[moon] });
+            for (seat, taken) in self.taken.iter_mut().enumerate() {
+                *taken = if seat == moon { 0 } else { ALL_POINTS };
+            }
+        }
+
+        for (score, taken) in self.scores.iter_mut().zip(&self.taken) {
+            *score += taken;
+        }
+        self.round += 1;
+        self.tricks_played = 0;
+        res.push(Event::RoundScores { round : self.round, points : self.taken.clone(), scores : self.scores.clone() });
+
+        self.over = self.scores.iter().any(|&s| s >= TARGET_SCORE);
+        if self.over {
+            return;
+        }
+        self.start_round();
+        res.push(Event::HandsDealt);
+        res.extend(self.round_events());
+    }
+
+    fn seat(&self, p_id : PlayerId) -> Option<usize> {
+        self.seats.iter().position(|&s| s == p_id)
+    }
+
+    fn two_of_clubs_holder(&self) -> usize {
+        self.hands.iter().position(|h| h.contains(&TWO_OF_CLUBS)).expect("the two of clubs is always dealt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    fn table(n : u32) -> Hearts {
+        Hearts::new((0..n).map(PlayerId).collect())
+    }
+
+    /// a round already under way with these hands , `turn` leads
+    fn playing(hands : Vec<Vec<Card>>, turn : usize, tricks_played : u32) -> Hearts {
+        let mut game = table(hands.len() as u32);
+        game.trick = Trick::new(hands.len(), None);
+        game.hands = hands;
+        game.turn = turn;
+        game.tricks_played = tricks_played;
+        game
+    }
+
+    #[test]
+    fn first_trick_opens_with_two_of_clubs_and_carries_no_points() {
+        let mut game = playing(vec![
+            vec![TWO_OF_CLUBS, card(Rank::Ace, Suit::Spade)],
+            vec![card(Rank::Five, Suit::Heart), card(Rank::Four, Suit::Diamond)],
+            vec![QUEEN_OF_SPADES, card(Rank::Six, Suit::Heart)],
+        ], 0, 0);
+        assert_eq!(game.legal_cards(PlayerId(0)), vec![TWO_OF_CLUBS]);
+        assert!(game.legal_cards(PlayerId(1)).is_empty());
+
+        game.play_card(PlayerId(0), TWO_OF_CLUBS);
+        assert_eq!(game.legal_cards(PlayerId(1)), vec![card(Rank::Four, Suit::Diamond)]);
+
+        // nothing but points , they have to go
+        game.play_card(PlayerId(1), card(Rank::Four, Suit::Diamond));
+        assert_eq!(game.legal_cards(PlayerId(2)).len(), 2);
+    }
+
+    #[test]
+    fn hearts_are_led_only_once_broken() {
+        let hand = vec![card(Rank::Ten, Suit::Heart), card(Rank::Three, Suit::Club)];
+        let mut game = playing(vec![hand.clone(), vec![card(Rank::Two, Suit::Spade)], vec![card(Rank::Two, Suit::Diamond)]], 0, 1);
+        assert_eq!(game.legal_cards(PlayerId(0)), vec![card(Rank::Three, Suit::Club)]);
+
+        game.hearts_broken = true;
+        assert_eq!(game.legal_cards(PlayerId(0)), hand);
+
+        // a hand of only hearts may lead them anyway
+        game.hearts_broken = false;
+        game.hands[0] = vec![card(Rank::Ten, Suit::Heart)];
+        assert_eq!(game.legal_cards(PlayerId(0)), vec![card(Rank::Ten, Suit::Heart)]);
+    }
+
+    #[test]
+    fn playing_a_heart_breaks_them() {
+        let mut game = playing(vec![
+            vec![card(Rank::Three, Suit::Club), card(Rank::Ace, Suit::Club)],
+            vec![card(Rank::Nine, Suit::Heart), card(Rank::Two, Suit::Heart)],
+            vec![card(Rank::Four, Suit::Club), card(Rank::Ace, Suit::Diamond)],
+        ], 0, 1);
+        game.play_card(PlayerId(0), card(Rank::Three, Suit::Club));
+        let events = game.play_card(PlayerId(1), card(Rank::Nine, Suit::Heart));
+        assert!(events.iter().any(|ev| matches!(ev, Event::HeartsBroken)));
+        assert!(game.hearts_broken);
+    }
+
+    #[test]
+    fn pass_direction_follows_the_round() {
+        let mut four = table(4);
+        let mut three = table(3);
+        let mut seen = (Vec::new(), Vec::new());
+        for round in 0..5 {
+            four.round = round;
+            three.round = round;
+            seen.0.push(four.direction());
+            seen.1.push(three.direction());
+        }
+        use PassDirection::*;
+        assert_eq!(seen.0, vec![Left, Right, Across, Hold, Left]);
+        assert_eq!(seen.1, vec![Left, Right, Hold, Left, Right]);
+    }
+
+    #[test]
+    fn passing_left_moves_cards_one_seat_on() {
+        let mut game = table(4);
+        game.start_round();
+        let passes : Vec<Vec<Card>> = game.hands.iter().map(|h| h[..PASS_COUNT].to_vec()).collect();
+        for (seat, cards) in passes.iter().enumerate() {
+            game.pass_cards(PlayerId(seat as u32), cards.clone());
+        }
+        for (seat, cards) in passes.iter().enumerate() {
+            assert!(cards.iter().all(|c| game.hands[(seat + 1) % 4].contains(c)));
+        }
+        assert!(!game.is_passing());
+    }
+
+    #[test]
+    fn shooting_the_moon_gives_everyone_else_the_points() {
+        let mut game = table(4);
+        game.taken = vec![0, ALL_POINTS, 0, 0];
+        let mut events = Vec::new();
+        game.end_round(&mut events);
+
+        assert!(events.iter().any(|ev| matches!(ev, Event::ShotTheMoon { p_id } if *p_id == PlayerId(1))));
+        assert_eq!(game.get_scores(), &[ALL_POINTS, 0, ALL_POINTS, ALL_POINTS]);
+    }
+
+    #[test]
+    fn three_players_leave_out_the_two_of_diamonds() {
+        let mut game = table(3);
+        game.start_round();
+        assert!(game.hands.iter().all(|h| h.len() == 17));
+        assert!(!game.hands.iter().flatten().any(|c| *c == card(Rank::Two, Suit::Diamond)));
+        assert!(game.hands.iter().flatten().any(|c| *c == TWO_OF_CLUBS));
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+use crate::game::card::{Card, Suit};
+use crate::game::event::Event;
+use crate::game::players::PlayerId;
+
+/// what everyone at a classic table can work out from the events , nothing private
+#[derive(Debug, Clone, Default)]
+pub struct Knowledge {
+    /// discarded with a finished trick , out of the game
+    gone : HashSet<Card>,
+    /// cards of the trick being played
+    table : Vec<Card>,
+    /// cards a player is known to hold , picked up with a foul or the rescue
+    known : HashMap<PlayerId, Vec<Card>>,
+    /// suits a player fouled on , none of their unknown cards can be of these
+    voids : HashMap<PlayerId, HashSet<Suit>>,
+}
+
+impl Knowledge {
+    pub fn observe(&mut self, ev : &Event) {
+        match ev {
+            Event::CardPlayed { card, p_id } => {
+                self.table.push(*card);
+                self.forget(*p_id, card);
+            }
+            Event::DiscardPile => {
+                self.gone.extend(self.table.drain(..));
+            }
+            Event::FoulGiven { from, to, cards } => {
+                // the led card is always first in the pile
+                if let Some(led) = cards.first() {
+                    self.voids.entry(*from).or_default().insert(led.suit);
+                }
+                self.table.clear();
+                self.known.entry(*to).or_default().extend(cards.iter().copied());
+            }
+            Event::SpecialEvent { p_id, card, from } => {
+                self.forget(*from, card);
+                self.known.entry(*p_id).or_default().push(*card);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_gone(&self, card : &Card) -> bool {
+        self.gone.contains(card)
+    }
+
+    pub fn get_known(&self, p_id : PlayerId) -> &[Card] {
+        self.known.get(&p_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn is_void(&self, p_id : PlayerId, suit : Suit) -> bool {
+        self.voids.get(&p_id).is_some_and(|suits| suits.contains(&suit))
+    }
+
+    fn forget(&mut self, p_id : PlayerId, card : &Card) {
+        if let Some(cards) = self.known.get_mut(&p_id) {
+            cards.retain(|c| c != card);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::Rank;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    #[test]
+    fn a_finished_trick_is_gone() {
+        let mut k = Knowledge::default();
+        let (nine, four) = (card(Rank::Nine, Suit::Spade), card(Rank::Four, Suit::Spade));
+        k.observe(&Event::CardPlayed { card : nine, p_id : PlayerId(0) });
+        k.observe(&Event::CardPlayed { card : four, p_id : PlayerId(1) });
+        assert!(!k.is_gone(&nine));
+
+        k.observe(&Event::DiscardPile);
+        assert!(k.is_gone(&nine) && k.is_gone(&four));
+    }
+
+    #[test]
+    fn a_foul_shows_a_void_and_who_took_the_cards() {
+        let mut k = Knowledge::default();
+        let (led, foul) = (card(Rank::Nine, Suit::Spade), card(Rank::Five, Suit::Heart));
+        k.observe(&Event::CardPlayed { card : led, p_id : PlayerId(0) });
+        k.observe(&Event::CardPlayed { card : foul, p_id : PlayerId(1) });
+        k.observe(&Event::FoulGiven { from : PlayerId(1), to : PlayerId(0), cards : vec![led, foul] });
+
+        assert!(k.is_void(PlayerId(1), Suit::Spade));
+        assert!(!k.is_void(PlayerId(1), Suit::Heart));
+        assert!(!k.is_void(PlayerId(0), Suit::Spade));
+        assert_eq!(k.get_known(PlayerId(0)), &[led, foul]);
+        // picked up , not thrown away
+        assert!(!k.is_gone(&led));
+
+        // playing a known card or losing it to the rescue takes it off the list
+        k.observe(&Event::CardPlayed { card : led, p_id : PlayerId(0) });
+        k.observe(&Event::SpecialEvent { p_id : PlayerId(2), card : foul, from : PlayerId(0) });
+        assert!(k.get_known(PlayerId(0)).is_empty());
+        assert_eq!(k.get_known(PlayerId(2)), &[foul]);
+    }
+}
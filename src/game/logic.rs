@@ -1,5 +1,3 @@
-use crate::game::card::Rank;
-use crate::game::card::Suit;
 use crate::game::players::PlayerId;
 use crate::game::actions::Action;
 use crate::game::event::Event;
@@ -7,34 +5,87 @@ use crate::game::players::Players;
 use crate::game::card::Card;
 use crate::game::card::shuffle;
 use crate::game::card::standard_deck;
+use crate::game::summary::{GameStats, GameSummary};
+use crate::game::settings::{GameMode, RoomSettings, CALLBREAK_PLAYERS, HEARTS_MIN_PLAYERS, MIN_PLAYERS, TEAM_PLAYERS};
+use crate::game::hearts::{Hearts, suggested_pass};
+use crate::game::callbreak::{Callbreak, suggested_bid};
+use crate::game::crazy_eights::CrazyEights;
+use crate::game::trick::Trick;
+use crate::game::knowledge::Knowledge;
+use crate::game::view::PlayerView;
+use crate::game::tutorial::{Scenario, Tutorial};
+use crate::game::rng;
+use crate::bot::profile::{BotProfile, Difficulty};
+use crate::bot::hint::{self, Hint};
+use crate::game::rules::FoulTo;
 use std::collections::HashSet;
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 // set line 231 ;;;; 323 too , first card must be played as ace of spade , i need to ensure that 
 // for reset game design player knowledge must be mainted , instead of removing players from the stack . mark them as eliminated
 // then change the code for next turn as winner functon as well as some other things to ensure correctness
 
 use rand::Rng;
+
+/// what the host hears when trying to change a table the server runs
+const MANAGED_TABLE : &str = "tournament and duplicate tables are run by the server";
+
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
 pub enum GamePhase{
 
     Waiting = 0,
     Playing = 1,
     Ended = 2,
 }
+/// state of the game being played when it is not the classic one
+#[derive(Clone)]
+enum Variant {
+    Classic,
+    Hearts(Hearts),
+    Callbreak(Callbreak),
+    CrazyEights(CrazyEights),
+}
+
+#[derive(Clone)]
 pub struct Game{
     phase : GamePhase,
     turn : PlayerId,
-    turn_stack : Vec<(Card , PlayerId)>,
+    turn_stack : Trick,
     players : Vec<Players>, // ready ko false krna h
     deck : Vec<Card>,
-    first : PlayerId,
     id_set : HashSet<PlayerId>,
     hand_dealt : bool, // isko false 
     first_move : bool, // isko false
+    stats : GameStats,
+    rematch_votes : HashSet<PlayerId>,
+    host : Option<PlayerId>,
+    settings : RoomSettings,
+    turn_started : Instant,
+    names_locked : bool,
+    /// deck order and seat rotation every game at this table is dealt from , None means shuffle
+    fixed_deal : Option<(Vec<Card>, usize)>,
+    /// hands in seat order and the seat that leads without A♠ , dealt instead of a deck
+    preset : Option<(Vec<Vec<Card>>, usize)>,
+    /// deck order of the current game before dealing
+    dealt : Vec<Card>,
+    variant : Variant,
+    /// what the players have seen so far , classic games only
+    knowledge : Knowledge,
+    /// practice games , the preset deal and the learner's progress
+    tutorial : Option<Tutorial>,
+    /// run by a tournament or duplicate set , the host can't kick , force start , seat bots or change settings
+    managed : bool,
     // iter : u32,
 }
 
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Game{
 
     // dead code
@@ -60,35 +111,64 @@ impl Game{
 
         Self{
             phase : GamePhase::Waiting,
-            turn_stack : Vec::new(),
+            turn_stack : Trick::default(),
             players : Vec::new(),
             deck : Vec::new(),
-            first : PlayerId(0),
             turn : PlayerId(0),
             // iter : 0,
             id_set,
             hand_dealt : false,
             first_move : false,
+            stats : GameStats::default(),
+            rematch_votes : HashSet::new(),
+            host : None,
+            settings : RoomSettings::default(),
+            turn_started : Instant::now(),
+            names_locked : false,
+            fixed_deal : None,
+            preset : None,
+            dealt : Vec::new(),
+            variant : Variant::Classic,
+            knowledge : Knowledge::default(),
+            tutorial : None,
+            managed : false,
+        }
+    }
+
+    pub fn with_settings(settings : RoomSettings) -> Self {
+        Self {
+            settings,
+            ..Self::new()
         }
     }
 
+    /// clears everything about the last deal , seats and names are kept
     fn reset(&mut self) {
-        self.phase = GamePhase::Waiting;
         self.deck = standard_deck();
         self.hand_dealt = false;
-        self.turn_stack.clear();
+        self.turn_stack = Trick::default();
         self.first_move = false;
-
-        for i in 0..4 {
-            self.id_set.insert(PlayerId(i));
-        }
+        self.rematch_votes.clear();
+        self.variant = Variant::Classic;
+        self.knowledge = Knowledge::default();
 
         for player in &mut self.players {
             player.hand.clear();
+            player.finished = false;
         }
 
     }
 
+    /// leaves the post game lobby , everyone has to ready up again
+    fn back_to_lobby(&mut self) -> Event {
+        self.reset();
+        self.phase = GamePhase::Waiting;
+        for player in &mut self.players {
+            player.ready = player.is_bot();
+        }
+        Event::ReturnToLobby
+    }
+
     // pub fn total_players(& self) -> usize {
     //     self.players.len()
     // }
@@ -98,23 +178,46 @@ impl Game{
     // }
 
     pub fn add_player(&mut self) -> Option<Event> {
-        if self.phase != GamePhase::Waiting || self.players.len() == 4{
+        let id = self.take_seat(Players::new)?;
+        // first one at the table runs it
+        if self.host.is_none() {
+            self.host = Some(id);
+        }
+        Some(Event::PlayerAdded { p_id: id })
+    }
+
+    /// seats a server controlled player , bots are always ready and never host
+    pub fn add_bot(&mut self, profile : BotProfile) -> Option<Event> {
+        let id = self.take_seat(|id| Players::new_bot(id, profile))?;
+        Some(Event::PlayerAdded { p_id: id })
+    }
+
+    fn take_seat(&mut self, new : impl FnOnce(PlayerId) -> Players) -> Option<PlayerId> {
+        if self.phase != GamePhase::Waiting || self.players.len() >= self.settings.max_players {
             return None;
         }
 
-        let id = self.id_set.iter().next().copied()?;
+        // lowest free id , the same table always seats the same way
+        let id = self.id_set.iter().copied().min_by_key(|p| p.0)?;
         self.id_set.remove(&id);
-        self.players.push(Players::new(id));
-        Some(Event::PlayerAdded { p_id: id })
+        self.players.push(new(id));
+        Some(id)
     }
 
-    pub fn remove_player(&mut self, player_id: PlayerId) -> Option<Event> {
+    pub fn remove_player(&mut self, player_id: PlayerId) -> Vec<Event> {
         if player_id.0 >= 4 || self.id_set.contains(&player_id){
-            return None;
+            return vec![];
         }
 
+        let mut res = Vec::new();
+
         if self.phase == GamePhase::Playing {
-            return Some(Event::AbortGame);
+            res.push(Event::AbortGame);
+            self.reset();
+            self.phase = GamePhase::Waiting;
+            for player in &mut self.players {
+                player.ready = player.is_bot();
+            }
         }
 
         // if player_id.0 >= 4 {
@@ -127,31 +230,334 @@ impl Game{
 
         self.id_set.insert(player_id);
         self.players.retain(|p| p.id != player_id);
-        return Some(Event::PlayerLeft { p_id: player_id })
+        self.rematch_votes.remove(&player_id);
+        res.push(Event::PlayerLeft { p_id: player_id });
+
+        if self.host == Some(player_id) {
+            self.host = self.players.iter().find(|p| !p.is_bot()).map(|p| p.id);
+            if let Some(p_id) = self.host {
+                res.push(Event::HostChanged { p_id });
+            }
+        }
+
+        // everyone still seated may already be ready , bots alone never start a game
+        if self.phase == GamePhase::Waiting && !self.is_empty() && self.all_ready() {
+            self.phase = GamePhase::Playing;
+            res.push(Event::StartGame);
+        }
+
+        if self.phase == GamePhase::Ended {
+            if self.players.len() < 2 {
+                res.push(self.back_to_lobby());
+            }
+            else if !self.is_empty() && self.all_voted_rematch() {
+                res.extend(self.start_rematch());
+            }
+        }
+        res
     }
 
     pub fn get_phase(& self) -> GamePhase{
         self.phase
     }
 
+    pub fn get_turn(& self) -> PlayerId {
+        self.turn
+    }
+
     pub fn get_seats(& self) -> Vec<PlayerId> {
         self.players.iter().map(|p| p.id).collect()
     }
 
+    pub fn get_names(& self) -> Vec<String> {
+        self.players.iter().map(|p| p.name.clone()).collect()
+    }
+
+    /// how each seat is played by the server , None for people , in seat order
+    pub fn get_bots(& self) -> Vec<Option<Difficulty>> {
+        self.players.iter().map(|p| p.bot.as_ref().map(|b| b.difficulty)).collect()
+    }
+
+    pub fn get_bot(& self, p_id : PlayerId) -> Option<BotProfile> {
+        self.players.iter().find(|p| p.id == p_id).and_then(|p| p.bot.clone())
+    }
+
+    /// everything `p_id` is allowed to know about the table , None if they are not seated
+    pub fn view_for(& self, p_id : PlayerId) -> Option<PlayerView> {
+        let seats = self.get_seats();
+        Some(PlayerView {
+            me : p_id,
+            mode : self.settings.mode,
+            hand : self.get_hand(p_id)?,
+            legal : self.legal_cards(p_id),
+            trick : self.get_trick().to_vec(),
+            hand_sizes : seats.iter().map(|&p| self.hand_len(p)).collect(),
+            finished : self.players.iter().filter(|p| p.finished).map(|p| p.id).collect(),
+            seats,
+            turn : self.turn,
+        })
+    }
+
+    /// true once `p_id` got rid of their cards
+    pub fn is_finished(& self, p_id : PlayerId) -> bool {
+        self.players.iter().any(|p| p.id == p_id && p.finished)
+    }
+
     /// returns true if cards are dealt else returns false
     pub fn cards_dealt(& self) -> bool {
-        return self.hand_dealt;
+        self.hand_dealt
     }
 
-    pub fn get_turn(& self) -> PlayerId {
-        self.turn
+    pub fn get_host(& self) -> Option<PlayerId> {
+        self.host
+    }
+
+    pub fn get_settings(& self) -> &RoomSettings {
+        &self.settings
+    }
+
+    /// true when no person is seated , bots alone don't keep a table alive
+    pub fn is_empty(& self) -> bool {
+        self.players.iter().all(|p| p.is_bot())
+    }
+
+    /// cards played in the current trick , in play order
+    pub fn get_trick(& self) -> &[(Card, PlayerId)] {
+        match &self.variant {
+            Variant::Hearts(hearts) => hearts.get_trick(),
+            Variant::Callbreak(callbreak) => callbreak.get_trick(),
+            Variant::CrazyEights(_) => &[],
+            Variant::Classic => self.turn_stack.plays(),
+        }
+    }
+
+    /// true until the first card of a classic game is down
+    pub fn is_opening(& self) -> bool {
+        matches!(self.variant, Variant::Classic) && self.phase == GamePhase::Playing && !self.first_move
+    }
+
+    /// public information about the classic game being played
+    pub fn get_knowledge(& self) -> &Knowledge {
+        &self.knowledge
+    }
+
+    /// number of cards `p_id` holds , everyone can count them
+    pub fn hand_len(& self, p_id : PlayerId) -> usize {
+        self.get_hand(p_id).map_or(0, |hand| hand.len())
+    }
+
+    /// swaps the hand of a player in a trick game , bots use it on a copy to try out a guess at the hidden cards
+    pub fn set_hand(&mut self, p_id : PlayerId, cards : Vec<Card>) {
+        match &mut self.variant {
+            Variant::Hearts(hearts) => hearts.set_hand(p_id, cards),
+            Variant::Callbreak(callbreak) => callbreak.set_hand(p_id, cards),
+            Variant::CrazyEights(_) => {}
+            Variant::Classic => {
+                if let Some(pos) = self.find_by_player_id(p_id) {
+                    self.players[pos].hand = cards;
+                }
+            }
+        }
+    }
+
+    /// true while hearts players still pick cards to pass
+    pub fn is_passing(& self) -> bool {
+        matches!(&self.variant, Variant::Hearts(hearts) if hearts.is_passing())
+    }
+
+    /// true when `p_id` is on turn in crazy eights without a card to play
+    pub fn must_draw(& self, p_id : PlayerId) -> bool {
+        matches!(&self.variant, Variant::CrazyEights(eights) if eights.must_draw(p_id))
+    }
+
+    /// true while callbreak players still bid
+    pub fn is_bidding(& self) -> bool {
+        matches!(&self.variant, Variant::Callbreak(callbreak) if callbreak.is_bidding())
+    }
+
+    /// what to announce once the cards are dealt , after SeatOrder
+    pub fn opening_events(& self) -> Vec<Event> {
+        match &self.variant {
+            Variant::Hearts(hearts) => hearts.round_events(),
+            Variant::Callbreak(callbreak) => callbreak.round_events(),
+            Variant::CrazyEights(eights) => eights.round_events(),
+            Variant::Classic => {
+                let mut events = self.tutorial.as_ref().map(|t| t.opening(self.turn)).unwrap_or_default();
+                events.insert(0, Event::NextTurn { player_id: self.turn });
+                events
+            }
+        }
+    }
+
+    /// the bot whose turn it is , if any
+    pub fn bot_to_move(& self) -> Option<PlayerId> {
+        if self.phase != GamePhase::Playing || !self.hand_dealt {
+            return None;
+        }
+        // everyone passes at once , bots take turns doing it
+        if let Variant::Hearts(hearts) = &self.variant
+            && hearts.is_passing() {
+            return self.players.iter().find(|p| p.is_bot() && !hearts.has_passed(p.id)).map(|p| p.id);
+        }
+        self.players.iter().find(|p| p.id == self.turn && p.is_bot()).map(|p| p.id)
+    }
+
+    /// when the current turn began , a different value means the game moved on
+    pub fn get_turn_started(&self) -> Instant {
+        self.turn_started
+    }
+
+    /// true when the player to move has used up the turn timer
+    pub fn turn_timed_out(&self, now : Instant) -> bool {
+        if self.phase != GamePhase::Playing || !self.hand_dealt {
+            return false;
+        }
+        match self.settings.turn_timer_secs {
+            Some(secs) => now.duration_since(self.turn_started) >= Duration::from_secs(secs),
+            None => false,
+        }
+    }
+
+    /// plays the lowest legal card for whoever is on turn , or passes for everyone still picking
+    pub fn auto_play(&mut self) -> Vec<Event> {
+        if self.is_passing() {
+            let mut res = Vec::new();
+            for p_id in self.get_seats() {
+                let Variant::Hearts(hearts) = &mut self.variant else {
+                    break;
+                };
+                if hearts.has_passed(p_id) {
+                    continue;
+                }
+                let cards = suggested_pass(&hearts.get_hand(p_id).unwrap_or_default());
+                res.push(Event::TurnTimedOut { p_id });
+                res.extend(self.apply_action(Action::PassCards { player_id: p_id, cards }));
+            }
+            return res;
+        }
+
+        if self.is_bidding() {
+            let p_id = self.turn;
+            let bid = suggested_bid(&self.get_hand(p_id).unwrap_or_default());
+            let mut res = vec![Event::TurnTimedOut { p_id }];
+            res.extend(self.apply_action(Action::Bid { player_id: p_id, bid }));
+            return res;
+        }
+
+        let p_id = self.turn;
+        if self.must_draw(p_id) {
+            let mut res = vec![Event::TurnTimedOut { p_id }];
+            res.extend(self.apply_action(Action::DrawCard { player_id: p_id }));
+            return res;
+        }
+
+        let rules = &self.settings.rules;
+        let Some(card) = self.legal_cards(p_id).into_iter().min_by_key(|c| rules.rank_value(c.rank)) else {
+            return vec![];
+        };
+
+        let mut res = vec![Event::TurnTimedOut { p_id }];
+        res.extend(self.apply_action(Action::CardPlayedByPlayer { player_id: p_id, card, suit: None }));
+        res
+    }
+
+    /// the cards `p_id` may play , best first with the reason , unless the host turned hints off
+    pub fn hints(&self, p_id : PlayerId) -> Result<Vec<Hint>, String> {
+        if !self.settings.hints {
+            return Err("hints are turned off in this room".into());
+        }
+        if self.phase != GamePhase::Playing {
+            return Err("no game is being played".into());
+        }
+        if self.must_draw(p_id) {
+            return Err("nothing fits , draw a card".into());
+        }
+        let hints = hint::rank(self, p_id);
+        if hints.is_empty() {
+            return Err("it is not your turn".into());
+        }
+        Ok(hints)
+    }
+
+    /// cards `p_id` is allowed to play right now , empty if it is not their turn
+    pub fn legal_cards(&self, p_id : PlayerId) -> Vec<Card> {
+        if self.phase != GamePhase::Playing {
+            return vec![];
+        }
+        match &self.variant {
+            Variant::Hearts(hearts) => return hearts.legal_cards(p_id),
+            Variant::Callbreak(callbreak) => return callbreak.legal_cards(p_id),
+            Variant::CrazyEights(eights) => return eights.legal_cards(p_id),
+            Variant::Classic => {}
+        }
+        if self.turn != p_id {
+            return vec![];
+        }
+        let Some(player) = self.players.iter().find(|p| p.id == p_id) else {
+            return vec![];
+        };
+
+        if !self.first_move {
+            return player.hand.iter().copied().filter(|c| self.settings.rules.can_open(c)).collect();
+        }
+
+        self.turn_stack.follow_cards(&player.hand)
     }
 
     pub fn start_game(&mut self) {
         self.reset();
+        self.phase = GamePhase::Playing;
+        self.stats = GameStats::new(self.get_seats());
+        self.turn_started = Instant::now();
+
+        match self.settings.mode {
+            GameMode::Hearts => {
+                let mut hearts = Hearts::new(self.get_seats());
+                hearts.start_round();
+                self.turn = hearts.get_turn();
+                self.variant = Variant::Hearts(hearts);
+                self.hand_dealt = true;
+                return;
+            }
+            GameMode::Callbreak => {
+                let mut callbreak = Callbreak::new(self.get_seats());
+                callbreak.start_round();
+                self.turn = callbreak.get_turn();
+                self.variant = Variant::Callbreak(callbreak);
+                self.hand_dealt = true;
+                return;
+            }
+            GameMode::CrazyEights => {
+                let eights = CrazyEights::new(self.get_seats());
+                self.turn = eights.get_turn();
+                self.variant = Variant::CrazyEights(eights);
+                self.hand_dealt = true;
+                return;
+            }
+            GameMode::Classic => {}
+        }
 
-        shuffle(&mut self.deck);
-        self.distribute();
+        if self.tutorial.is_some() {
+            self.deal_scenario();
+            return;
+        }
+        if let Some((hands, leader)) = self.preset.clone() {
+            self.deal_hands(hands, 0, leader);
+            return;
+        }
+
+        let rotation = match &self.fixed_deal {
+            Some((deck, rotation)) => {
+                self.deck = deck.clone();
+                *rotation
+            }
+            None => {
+                shuffle(&mut self.deck);
+                0
+            }
+        };
+        self.dealt = self.deck.clone();
+        self.distribute(rotation);
 
         self.hand_dealt = true;
 
@@ -159,10 +565,15 @@ impl Game{
             .expect("there must be a player with Ace of Spade");
 
         self.turn = first;
-        self.first = first;
     }
 
     pub fn get_hand(&self, player_id: PlayerId) -> Option<Vec<Card>> {
+        match &self.variant {
+            Variant::Hearts(hearts) => return hearts.get_hand(player_id),
+            Variant::Callbreak(callbreak) => return callbreak.get_hand(player_id),
+            Variant::CrazyEights(eights) => return eights.get_hand(player_id),
+            Variant::Classic => {}
+        }
         self.players
             .iter()
             .find(|p| p.id == player_id)
@@ -172,23 +583,44 @@ impl Game{
 
 
     pub fn apply_action(&mut self, action: Action) -> Vec<Event> {
+        let mut events = self.handle_action(action);
+
+        if matches!(self.variant, Variant::Classic) {
+            for ev in &events {
+                self.knowledge.observe(ev);
+            }
+        }
+        if let Some(tutorial) = &mut self.tutorial {
+            let more = tutorial.observe(&events);
+            events.extend(more);
+        }
+
+        // restart the turn clock whenever the turn moves on
+        if events.iter().any(|ev| matches!(ev, Event::NextTurn { .. } | Event::PassingStarted { .. } | Event::NextBid { .. })) {
+            self.turn_started = Instant::now();
+        }
+        events
+    }
+
+    fn handle_action(&mut self, action: Action) -> Vec<Event> {
         match action {
-            Action::Ready { player_id } => {
+            Action::Ready { player_id , name } => {
                 let mut v : Vec<Event> = Vec::new();
                 if self.phase != GamePhase::Waiting {
                     return v;
                 }
-                if !self.mark_ready(player_id) {
+                if !self.mark_ready(player_id, name) {
                     return v;
                 }
 
-                v.push(Event::MarkReady { p_id: player_id });
+                let name = self.get_name(player_id).unwrap_or_default();
+                v.push(Event::MarkReady { p_id: player_id , name });
 
                 if self.all_ready() {
                     self.phase = GamePhase::Playing;
                     v.push(Event::StartGame);
                 }
-                return v;
+                v
             }
             // Action::StartGame => {
             //     if self.players.len() < 2 {
@@ -224,156 +656,296 @@ impl Game{
             //     ]
             // }
             
-            Action::EndGame => {
-                self.phase = GamePhase::Waiting;
-                return vec![];
+            Action::Unready { player_id } => {
+                if self.phase != GamePhase::Waiting {
+                    return vec![];
+                }
+                match self.players.iter_mut().find(|p| p.id == player_id) {
+                    Some(p) if p.ready => {
+                        p.ready = false;
+                        vec![Event::MarkUnready { p_id: player_id }]
+                    }
+                    _ => vec![],
+                }
             }
 
-            Action::CardPlayedByPlayer { player_id, card } => {
-                //0. first card must be ace of spade
-         
-                if self.first_move == false {
-                    if card.rank != Rank::Ace || card.suit != Suit::Spade {
-                        return vec![Event::InvalidCard { p_id: (player_id) }]
-                    }
-                    else {
-                        self.first_move = true;
-                    }
+            Action::LeaveTable { player_id } => self.remove_player(player_id),
+
+            Action::UpdateSettings { player_id, settings } => {
+                if self.phase != GamePhase::Waiting || self.host != Some(player_id) {
+                    return vec![];
+                }
+                if self.managed {
+                    return vec![Event::Error { message: MANAGED_TABLE.into() }];
+                }
+                // scenarios are written for one table size and the default house rules
+                if self.tutorial.is_some() {
+                    return vec![Event::Error { message: "practice rooms keep the scenario's settings".into() }];
+                }
+                if let Err(message) = settings.validate(self.players.len()) {
+                    return vec![Event::Error { message }];
+                }
+                // every table of a duplicate set has to play the same game
+                if self.fixed_deal.is_some() {
+                    return vec![Event::Error { message: "duplicate tables keep their settings".into() }];
+                }
+                self.settings = settings.clone();
+                vec![Event::SettingsChanged { settings }]
+            }
+
+            Action::TransferHost { player_id, to } => {
+                if self.host != Some(player_id) || player_id == to || !self.players.iter().any(|p| p.id == to && !p.is_bot()) {
+                    return vec![];
+                }
+                self.host = Some(to);
+                vec![Event::HostChanged { p_id: to }]
+            }
+
+            Action::KickPlayer { player_id, target } => {
+                if self.phase == GamePhase::Playing || self.host != Some(player_id) || player_id == target {
+                    return vec![];
+                }
+                if self.managed {
+                    return vec![Event::Error { message: MANAGED_TABLE.into() }];
+                }
+                if self.find_by_player_id(target).is_none() {
+                    return vec![];
+                }
+                let mut v = vec![Event::PlayerKicked { p_id: target }];
+                v.extend(self.remove_player(target));
+                v
+            }
+
+            Action::AddBot { player_id, profile } => {
+                if self.phase != GamePhase::Waiting || self.host != Some(player_id) {
+                    return vec![];
+                }
+                if self.managed {
+                    return vec![Event::Error { message: MANAGED_TABLE.into() }];
+                }
+                if let Err(message) = profile.validate() {
+                    return vec![Event::Error { message }];
+                }
+                let Some(added) = self.add_bot(profile) else {
+                    return vec![Event::Error { message: "the table is full".into() }];
+                };
+                let mut v = vec![added];
+                // the bot may have been the seat everyone was waiting for
+                if self.all_ready() {
+                    self.phase = GamePhase::Playing;
+                    v.push(Event::StartGame);
+                }
+                v
+            }
+
+            // starts with everyone seated once enough of them are ready
+            Action::ForceStart { player_id } => {
+                if self.phase != GamePhase::Waiting || self.host != Some(player_id) {
+                    return vec![];
+                }
+                if self.managed {
+                    return vec![Event::Error { message: MANAGED_TABLE.into() }];
+                }
+                // asking to start counts as being ready
+                let ready = self.players.iter().filter(|p| p.ready || p.id == player_id).count();
+                if ready < MIN_PLAYERS {
+                    return vec![Event::Error { message: format!("at least {MIN_PLAYERS} players must be ready") }];
+                }
+                if let Some(message) = self.seating_problem(ready) {
+                    return vec![Event::Error { message }];
+                }
+
+                let mut v = Vec::new();
+                let host_ready = self.players.iter().any(|p| p.id == player_id && p.ready);
+                if !host_ready && self.mark_ready(player_id, None) {
+                    let name = self.get_name(player_id).unwrap_or_default();
+                    v.push(Event::MarkReady { p_id: player_id, name });
+                }
+                // whoever isn't ready gives up the seat , like a kick
+                let unready : Vec<PlayerId> = self.players.iter().filter(|p| !p.ready).map(|p| p.id).collect();
+                for p_id in unready {
+                    v.push(Event::PlayerKicked { p_id });
+                    v.extend(self.remove_player(p_id));
+                }
+                // the last one leaving may already have started it
+                if self.phase == GamePhase::Waiting {
+                    self.phase = GamePhase::Playing;
+                    v.push(Event::StartGame);
                 }
-          
+                v
+            }
+
+            // closes the post game lobby without a rematch
+            Action::EndGame { player_id } => {
+                if self.phase != GamePhase::Ended || self.find_by_player_id(player_id).is_none() {
+                    return vec![];
+                }
+                // a vote for the rematch can't be taken back by cancelling it , unless you run the table
+                if self.rematch_votes.contains(&player_id) && self.host != Some(player_id) {
+                    return vec![];
+                }
+                vec![self.back_to_lobby()]
+            }
+
+            Action::Rematch { player_id } => {
+                if self.phase != GamePhase::Ended || self.find_by_player_id(player_id).is_none() {
+                    return vec![];
+                }
+                if !self.rematch_votes.insert(player_id) {
+                    return vec![];
+                }
+
+                let mut v = vec![Event::RematchVote { p_id: player_id }];
+                if self.all_voted_rematch() {
+                    v.extend(self.start_rematch());
+                }
+                v
+            }
+
+            // nothing changes at the table , the socket answers privately with `hints`
+            Action::Hint { .. } => vec![],
+
+            Action::PassCards { .. } => {
+                if self.phase != GamePhase::Playing {
+                    return vec![];
+                }
+                self.handle_variant(action)
+            }
+
+            Action::Bid { .. } | Action::DrawCard { .. } => {
+                if self.phase != GamePhase::Playing {
+                    return vec![];
+                }
+                self.handle_variant(action)
+            }
+
+            Action::CardPlayedByPlayer { player_id, card, .. } => {
+                if self.phase != GamePhase::Playing {
+                    return vec![];
+                }
+                if !matches!(self.variant, Variant::Classic) {
+                    return self.handle_variant(action);
+                }
+
+                // practice games only take the move the scenario asks for
+                if let Some(message) = self.tutorial.as_ref().and_then(|t| t.check(player_id, self.turn, &card)) {
+                    return vec![Event::TutorialMistake { p_id: player_id, card, message }];
+                }
+
                 // 1. Find player
-                let player = match self.players.iter_mut().find(|p| p.id == player_id) {
-                    Some(p) => p,
-                    None => {
-                        return vec![Event::Error {
-                            message: "Player not found".to_string(), // or into();
-                        }];
-                    }
+                let Some(pos) = self.find_by_player_id(player_id) else {
+                    return vec![Event::Error {
+                        message: "Player not found".to_string(), // or into();
+                    }];
                 };
-         
+
                 //2. Check if its player's turn or not
-                if self.turn != player.id {
+                if self.turn != player_id {
                     return vec![Event::InvalidPlayer];
                 }
-          
-                // 3. Check if card exists in hand
 
-                if !player.hand.iter().any(|c| *c == card) {
+                // 3. Check if card exists in hand
+                if !self.players[pos].hand.contains(&card) {
                     return vec![Event::AbortGame];
                 }
 
-                let pos = match self.players.iter().position(|p| p.id == player_id) {
-                    Some(i) => i,
-                    None => {
-                        return vec![Event::InvalidPlayer];
+                //4. first card must be ace of spade , unless the house rules say otherwise
+                if !self.first_move {
+                    if !self.settings.rules.can_open(&card) {
+                        return vec![Event::InvalidCard { p_id: (player_id) }]
                     }
-                };
+                    self.first_move = true;
+                }
 
-                let next_turn = self.players[(pos+1)%self.players.len()].id;
+                let next_turn = self.players[self.next_active(pos)].id;
+                let mut res:Vec<Event> = Vec::new();
 
-                // first turn
-                if self.turn_stack.len() == 0 {
-                    self.turn_stack.push((Players::remove_card(&mut self.players[pos] , &card).expect("player played a valid card")
- , player_id));
-                    // self.players[pos].hand.remove(self.players[pos].hand.iter().position(|c| *c == card).unwrap());
-                    self.turn = next_turn;
-                    return vec![Event::CardPlayed{card : card , p_id : player_id } , Event::NextTurn { player_id:next_turn }]
+                // a trick goes once round everyone still holding cards
+                if self.turn_stack.is_empty() {
+                    let active = self.players.iter().filter(|p| !p.finished).count();
+                    self.turn_stack = Trick::new(active, None);
                 }
-                
-                let mut res:Vec<Event> = Vec::new();
-                if let Some((top_card , __p_id)) =  self.turn_stack.last() {
-                    // played card has same suit as top
-                    if card.suit == top_card.suit {
-                        self.turn_stack.push((Players::remove_card(&mut self.players[pos], &card).expect("already checked card exists at L136") , player_id));
-
-                        res.push(Event::CardPlayed { card : card , p_id : player_id });
-
-                        if next_turn == self.first {
-                            self.check_turn();
-                            self.first = self.turn;
-                            res.push(Event::DiscardPile);
-                            self.turn_stack.clear();
-                            let winners = self.check_winner();
-                            
-                            self.players.retain(|p| !winners.contains(&p.id));
-
-                            for winner in winners {
-                                res.push(Event::PlayerWon { player_id : winner });
-                            }
-
-                            if self.players.len() == 1 {
-                                res.push(Event::EndGame { p_id: self.players.first().expect("last players").id });
-                                self.reset();
-                                return res;
-                            }
-
-                            let pos = self.players.iter().position(|p| p.id == self.turn).expect("there is at least two players");
-                            // split at mut to use two mutable reference from same instance // not needed now ,, i am smart 
-                            if self.players[pos].hand.is_empty() {
-                                let curr_pos = pos;
-                                let next_pos = (pos+1)%self.players.len();
-                                let c = self.give_random_card(curr_pos , next_pos);
-                                
-                                res.push(Event::SpecialEvent { p_id: (player_id), card : c, from: (self.players[next_pos].id) });
-                            }
-                        }
-                        else{
-                            self.turn = next_turn;
-                        }
-                        res.push(Event::NextTurn { player_id : self.turn });
 
+                //check if player lying , a card off suit while holding the led suit
+                if !self.turn_stack.is_legal(&card, &self.players[pos].hand) {
+                    return vec![Event::InvalidCard {p_id : player_id}];
+                }
+
+                // played card has different suit and the player is void , means a foul
+                if self.turn_stack.led_suit().is_some_and(|led| card.suit != led) {
+                    // player played a valid foul , give all cards in turn stack to the playerid who played highest card
+                    let played = Players::remove_card(&mut self.players[pos], &card)
+                                                .expect("card exists in hand (checked earlier)");
+                    res.push(Event::CardPlayed { card, p_id: player_id });
+                    self.stats.record_turn();
+
+                    let p_id = match self.settings.rules.foul_to {
+                        FoulTo::Highest => self.trick_winner(),
+                        FoulTo::Leader => self.turn_stack.plays()[0].1,
+                    };
+                    let mut foul:Vec<Card> = self.turn_stack.take().into_iter().map(|(c , __)| c).collect();
+                    foul.push(played);
+
+                    res.push(Event::FoulGiven { from: (player_id), to: (p_id), cards: foul.clone() });
+                    self.stats.record_foul(player_id, p_id);
+
+                    self.add_cards_to_player(&p_id, foul);
+                    self.turn = p_id;
+                    // give foul as well as turn to next player , check winners and game end, no need to check exception condition as players hands gonna be full after a foul
+                    if self.remove_winners(&mut res) {
                         return res;
-                    } 
-                    // played card has different suit , means a foul or player lie
-                    else{
-                        //check if player lying
-                        let (top_card , __p_id) = self.turn_stack.last().expect("can't be none as turn stack is checked");
-                        let exist = self.players[pos].hand.iter().any(|c| c.suit == top_card.suit);
-                        if exist {
-                            return vec![Event::InvalidCard {p_id : player_id}];
-                        }
-                        // player played a valid foul , give all cards in turn stack to the playerid who played highest card
-                        let played = Players::remove_card(&mut self.players[pos], &card)
-                                                    .expect("card exists in hand (checked earlier)");
-                        res.push(Event::CardPlayed { card : card, p_id: player_id });
-                    
-                        let p_id = self.turn_stack.iter().max_by_key(|(c , __) | c.rank).expect("stack is not empty").1;
-                        let mut foul:Vec<Card> = self.turn_stack.drain(..).map(|(c , __)| c).collect();
-                        foul.push(played);
-
-                        res.push(Event::FoulGiven { from: (player_id), to: (p_id), cards: foul.clone() });
-
-                        self.add_cards_to_player(&p_id, foul);
-                        self.turn = p_id;
-                        self.first = self.turn; // nice error diddy
-                        // give foul as well as turn to next player , check winners and game end, no need to check exception condition as players hands gonna be full after a foul
-                        let winners = self.check_winner();
-                            
-                        self.players.retain(|p| !winners.contains(&p.id));
-
-                        for winner in winners {
-                            res.push(Event::PlayerWon { player_id : winner });
-                        }
-
-                        if self.players.len() == 1 {
-                            res.push(Event::EndGame { p_id: self.players.first().expect("last players").id });
-                            self.reset();
-                            return res;
-                        }
-
-                        res.push(Event::NextTurn { player_id : self.turn });
+                    }
+
+                    res.push(Event::NextTurn { player_id : self.turn });
+                    return res;
+                }
+
+                self.turn_stack.play(Players::remove_card(&mut self.players[pos], &card).expect("already checked card exists") , player_id);
+                res.push(Event::CardPlayed { card, p_id : player_id });
+                self.stats.record_turn();
+
+                if !self.turn_stack.is_complete() {
+                    self.turn = next_turn;
+                    res.push(Event::NextTurn { player_id : self.turn });
+                    return res;
+                }
+
+                self.check_turn();
+                res.push(Event::DiscardPile);
+                self.turn_stack.take();
+                self.stats.record_discard();
+
+                if self.remove_winners(&mut res) {
+                    return res;
+                }
+
+                let pos = self.find_by_player_id(self.turn).expect("there is at least two players");
+                if self.players[pos].finished {
+                    // went out with the last trick and nobody hands them a card , the lead moves on
+                    self.turn = self.players[self.next_active(pos)].id;
+                }
+                else if self.players[pos].hand.is_empty() {
+                    let curr_pos = pos;
+                    let next_pos = self.next_active(pos);
+                    let c = self.give_random_card(curr_pos , next_pos);
+
+                    res.push(Event::SpecialEvent { p_id: (self.players[curr_pos].id), card : c, from: (self.players[next_pos].id) });
+                    // that may have been the last card of the one handing it over
+                    if self.remove_winners(&mut res) {
                         return res;
                     }
                 }
-                return vec![];
+                res.push(Event::NextTurn { player_id : self.turn });
+                res
 
             }
         }
     }
-    pub fn distribute(&mut self) {
+    /// deals the whole deck round the table , the first card goes to seat `rotation`
+    pub fn distribute(&mut self, rotation : usize) {
         let n = self.players.len();
 
-        let mut i = 0;
+        let mut i = rotation;
 
         while let Some(card) = self.deck.pop() {
             let player = &mut self.players[i % n];
@@ -381,17 +953,124 @@ impl Game{
             i += 1;
         }
     }
-    pub fn mark_ready(& mut self , p_id : PlayerId) -> bool {
+
+    /// every following game deals the hands of `scenario` instead of shuffling , and checks the moves it asks for
+    pub fn set_tutorial(&mut self, scenario : &'static Scenario) {
+        self.tutorial = Some(Tutorial::new(scenario));
+    }
+
+    /// hands go round from the first person seated , the learner
+    fn deal_scenario(&mut self) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        let scenario = tutorial.get_scenario();
+        let learner = self.players.iter().position(|p| !p.is_bot()).unwrap_or(0);
+        tutorial.restart(self.players[learner].id);
+        self.deal_hands(scenario.hands(), learner, scenario.leader);
+    }
+
+    /// every following classic game deals `hands` , the first to the first seat and so on , instead of shuffling.
+    /// when nobody holds A♠ the game counts as opened and seat `leader` starts
+    pub fn set_preset_hands(&mut self, hands : Vec<Vec<Card>>, leader : usize) {
+        self.preset = Some((hands, leader));
+    }
+
+    /// `hands` go round the table from seat `from` , `leader` counts seats after it
+    fn deal_hands(&mut self, hands : Vec<Vec<Card>>, from : usize, leader : usize) {
+        let n = self.players.len();
+        for (i, hand) in hands.into_iter().enumerate() {
+            self.players[(from + i) % n].hand = hand;
+        }
+        self.dealt = self.players.iter().flat_map(|p| p.hand.clone()).collect();
+        self.hand_dealt = true;
+
+        // without A♠ in the deal the game is already under way
+        let ace = self.find_ace_of_spades_holder();
+        self.first_move = ace.is_none();
+        self.turn = ace.unwrap_or(self.players[(from + leader) % n].id);
+    }
+
+    /// hands the seats and settings to the server , see `managed`
+    pub fn set_managed(&mut self) {
+        self.managed = true;
+    }
+
+    /// every following game deals `deck` in this exact order , shifted by `rotation` seats
+    pub fn set_fixed_deal(&mut self, deck : Vec<Card>, rotation : usize) {
+        self.fixed_deal = Some((deck, rotation));
+    }
+    pub fn mark_ready(& mut self , p_id : PlayerId , name : Option<String>) -> bool {
         if let Some(p) = self.players.iter_mut().find(|p| p.id == p_id) {
             p.ready = true;
+            if let Some(name) = name.filter(|_| !self.names_locked) {
+                p.set_name(&name);
+            }
             return true;
         }
         false
     }
 
+    pub fn set_name(& mut self , p_id : PlayerId , name : &str) {
+        if let Some(p) = self.players.iter_mut().find(|p| p.id == p_id) {
+            p.set_name(name);
+        }
+    }
+
+    /// names given on join stay , used when a seat belongs to a known person
+    pub fn lock_names(& mut self) {
+        self.names_locked = true;
+    }
+
+    pub fn get_name(&self, p_id : PlayerId) -> Option<String> {
+        self.players.iter().find(|p| p.id == p_id).map(|p| p.name.clone())
+    }
+
+    fn all_voted_rematch(&self) -> bool {
+        // bots are always up for another one
+        self.players.len() > 1 && self.table_complete() && self.players.iter().all(|p| p.is_bot() || self.rematch_votes.contains(&p.id))
+    }
+
+    /// same seats , same names , fresh deal
+    fn start_rematch(&mut self) -> Vec<Event> {
+        self.reset();
+        self.phase = GamePhase::Playing;
+        vec![Event::Rematch, Event::StartGame]
+    }
+
 
     fn all_ready(&self) -> bool {
-        self.players.len() > 1 && self.players.iter().all(|p| p.ready)
+        self.players.len() > 1 && self.table_complete() && self.players.iter().all(|p| p.ready)
+    }
+
+    fn table_complete(&self) -> bool {
+        self.seating_problem(self.players.len()).is_none()
+    }
+
+    /// why `seated` players can't start the chosen game , team games and duplicate tables need every seat taken
+    fn seating_problem(&self, seated : usize) -> Option<String> {
+        if self.fixed_deal.is_some() && seated != self.settings.max_players {
+            return Some(format!("duplicate tables need all {} seats taken", self.settings.max_players));
+        }
+        if self.settings.team_mode && seated != TEAM_PLAYERS {
+            return Some(format!("team games need exactly {TEAM_PLAYERS} players"));
+        }
+        if self.settings.mode == GameMode::Hearts && seated < HEARTS_MIN_PLAYERS {
+            return Some(format!("hearts needs at least {HEARTS_MIN_PLAYERS} players"));
+        }
+        if self.settings.mode == GameMode::Callbreak && seated != CALLBREAK_PLAYERS {
+            return Some(format!("callbreak needs exactly {CALLBREAK_PLAYERS} players"));
+        }
+        None
+    }
+
+    /// partners sit opposite each other , seats 0 and 2 against 1 and 3
+    pub fn get_teams(&self) -> Option<Vec<Vec<PlayerId>>> {
+        if !self.settings.team_mode || self.players.len() != TEAM_PLAYERS {
+            return None;
+        }
+        let seats = self.get_seats();
+        Some(vec![vec![seats[0], seats[2]], vec![seats[1], seats[3]]])
     }
 
     fn find_ace_of_spades_holder(&self) -> Option<PlayerId> {
@@ -419,23 +1098,474 @@ impl Game{
     }
 
     fn check_turn(&mut self) {
-        self.turn = self.turn_stack.iter().max_by_key(|(c , _)| c.rank).expect("return a valid person to receive turn").1;
+        self.turn = self.trick_winner();
+    }
+
+    /// whoever played the highest card of the trick
+    fn trick_winner(&self) -> PlayerId {
+        let rules = &self.settings.rules;
+        self.turn_stack.winner_by(|rank| rules.rank_value(rank)).expect("return a valid person to receive turn").1
     }
 
+    /// players out of cards , the one on turn stays in when the rescue will hand them a card
     fn check_winner(&mut self)-> Vec<PlayerId>{
-        self.players.iter().filter(|p| p.hand.is_empty() && self.turn != p.id).map(|p| p.id).collect()
+        let rescue = self.settings.rules.empty_hand_rescue;
+        self.players.iter().filter(|p| !p.finished && p.hand.is_empty() && !(rescue && self.turn == p.id)).map(|p| p.id).collect()
+    }
+
+    /// index of the next seat after `pos` that is still in the game
+    fn next_active(&self, pos : usize) -> usize {
+        let n = self.players.len();
+        (1..=n)
+            .map(|i| (pos + i) % n)
+            .find(|&i| !self.players[i].finished)
+            .unwrap_or(pos)
+    }
+
+    /// takes players with empty hands off the table , returns true when the game is over
+    fn remove_winners(&mut self, res : &mut Vec<Event>) -> bool {
+        let winners = self.check_winner();
+
+        for player in &mut self.players {
+            if winners.contains(&player.id) {
+                player.finished = true;
+            }
+        }
+
+        for winner in winners {
+            self.stats.record_finish(winner);
+            res.push(Event::PlayerWon { player_id : winner });
+        }
+
+        let (loser, losing_team) = match self.get_teams() {
+            Some(teams) => {
+                let finished = |p_id : &PlayerId| self.players.iter().any(|p| p.id == *p_id && p.finished);
+                let out = |team : &Vec<PlayerId>| team.iter().all(finished);
+                // everyone went out on the same trick , its winner would have led next so their team takes the loss
+                let safe = if teams.iter().all(out) {
+                    teams.iter().position(|team| !team.contains(&self.turn))
+                } else {
+                    teams.iter().position(out)
+                };
+                let Some(safe) = safe else {
+                    return false;
+                };
+                res.push(Event::TeamSafe { team: safe, players: teams[safe].clone() });
+
+                // the other team still holds cards , the first of them still playing takes the loss
+                let losing = 1 - safe;
+                let loser = teams[losing].iter().copied().find(|p_id| !finished(p_id)).unwrap_or(teams[losing][0]);
+                (loser, Some(losing))
+            }
+            None => {
+                let mut left = self.players.iter().filter(|p| !p.finished);
+                match (left.next(), left.next()) {
+                    (Some(p), None) => (p.id, None),
+                    // the last cards went on the same trick , its winner would have led next and takes the loss
+                    (None, _) => (self.turn, None),
+                    _ => return false,
+                }
+            }
+        };
+
+        let mut summary = self.stats.summary(loser, |p_id| self.get_hand(p_id).unwrap_or_default());
+        // the other tables of a duplicate set may not have played this deal yet
+        if self.fixed_deal.is_none() {
+            summary.deal = self.dealt.clone();
+        }
+        summary.teams = self.get_teams().unwrap_or_default();
+        summary.losing_team = losing_team;
+        self.finish(summary, res);
+        true
+    }
+
+    /// announces the result , it stays up until everyone votes for a rematch or the table is closed
+    fn finish(&mut self, mut summary : GameSummary, res : &mut Vec<Event>) {
+        for stats in &mut summary.players {
+            stats.name = self.get_name(stats.p_id).unwrap_or_default();
+        }
+        let loser = summary.loser;
+        res.push(Event::GameOver { summary });
+        res.push(Event::EndGame { p_id: loser });
+
+        self.phase = GamePhase::Ended;
+        self.rematch_votes.clear();
+        for player in &mut self.players {
+            player.ready = player.is_bot();
+        }
+    }
+
+    /// hands an action to the running hearts or callbreak game , ends the game once it is over
+    fn handle_variant(&mut self, action : Action) -> Vec<Event> {
+        let mut res = match (&mut self.variant, action) {
+            (Variant::Hearts(hearts), Action::PassCards { player_id, cards }) => hearts.pass_cards(player_id, cards),
+            (Variant::Hearts(hearts), Action::CardPlayedByPlayer { player_id, card, .. }) => hearts.play_card(player_id, card),
+            (Variant::Callbreak(callbreak), Action::Bid { player_id, bid }) => callbreak.bid(player_id, bid),
+            (Variant::Callbreak(callbreak), Action::CardPlayedByPlayer { player_id, card, .. }) => callbreak.play_card(player_id, card),
+            (Variant::CrazyEights(eights), Action::CardPlayedByPlayer { player_id, card, suit }) => eights.play_card(player_id, card, suit),
+            (Variant::CrazyEights(eights), Action::DrawCard { player_id }) => eights.draw(player_id),
+            _ => return vec![],
+        };
+
+        for ev in &res {
+            match ev {
+                Event::CardPlayed { .. } => self.stats.record_turn(),
+                Event::TrickWon { .. } => self.stats.record_discard(),
+                _ => {}
+            }
+        }
+
+        let (turn, result) = match &self.variant {
+            Variant::Hearts(hearts) => {
+                let scores = hearts.get_scores().iter().map(|&s| s as f64).collect();
+                (hearts.get_turn(), hearts.is_over().then(|| (hearts.standings(), scores)))
+            }
+            Variant::Callbreak(callbreak) => (callbreak.get_turn(), callbreak.is_over().then(|| (callbreak.standings(), callbreak.get_scores()))),
+            Variant::CrazyEights(eights) => (eights.get_turn(), eights.is_over().then(|| (eights.standings(), eights.get_scores()))),
+            Variant::Classic => return res,
+        };
+        self.turn = turn;
+        let Some((mut order, scores)) = result else {
+            return res;
+        };
+
+        // standings are best first , the last one loses
+        let loser = order.pop().expect("the game has players");
+        for p_id in order {
+            self.stats.record_finish(p_id);
+        }
+        let mut summary = self.stats.summary(loser, |p_id| self.get_hand(p_id).unwrap_or_default());
+        summary.scores = scores;
+        self.finish(summary, &mut res);
+        res
     }
 
     pub fn give_random_card(&mut self , to : usize ,from :usize) -> Card {
         assert!(to != from, "cannot give card to same player");
-        let mut rng = rand::rng();
         let size = self.players[from].hand.len();
 
-        let idx = rng.random_range(0..size);
+        let idx = rng::with(|rng| rng.random_range(0..size));
         
         let card = self.players[from].hand.swap_remove(idx);
 
-        self.players[to].hand.push(card.clone());
+        self.players[to].hand.push(card);
         card
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::{Rank, Suit};
+    use crate::game::rules::HouseRules;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    fn play(game : &mut Game, player_id : PlayerId, card : Card) -> Vec<Event> {
+        game.apply_action(Action::CardPlayedByPlayer { player_id, card, suit : None })
+    }
+
+    /// three seats , the A♠ holder leads A♠ and everyone follows , returns the seats in play order
+    fn ace_wins_the_trick(rescue : bool, second : Vec<Card>) -> (Game, [PlayerId; 3], Vec<Event>) {
+        let rules = HouseRules { empty_hand_rescue : rescue, ..HouseRules::default() };
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, rules, ..RoomSettings::default() });
+        for _ in 0..3 {
+            game.add_player();
+        }
+        game.start_game();
+
+        let lead = game.find_by_player_id(game.turn).expect("A♠ is dealt");
+        let seat = |i : usize| (lead + i) % 3;
+        game.players[seat(0)].hand = vec![card(Rank::Ace, Suit::Spade)];
+        game.players[seat(1)].hand = second;
+        game.players[seat(2)].hand = vec![card(Rank::Nine, Suit::Spade), card(Rank::Eight, Suit::Heart)];
+        let order = [0, 1, 2].map(|i| game.players[seat(i)].id);
+
+        play(&mut game, order[0], card(Rank::Ace, Suit::Spade));
+        play(&mut game, order[1], card(Rank::Five, Suit::Spade));
+        let events = play(&mut game, order[2], card(Rank::Nine, Suit::Spade));
+        (game, order, events)
+    }
+
+    #[test]
+    fn trick_winner_loses_when_everyone_goes_out_together() {
+        let rules = HouseRules { empty_hand_rescue : false, ..HouseRules::default() };
+        let mut game = Game::with_settings(RoomSettings { max_players : 2, rules, ..RoomSettings::default() });
+        game.add_player();
+        game.add_player();
+        game.start_game();
+
+        let lead = game.find_by_player_id(game.turn).expect("A♠ is dealt");
+        game.players[lead].hand = vec![card(Rank::Ace, Suit::Spade)];
+        game.players[1 - lead].hand = vec![card(Rank::Five, Suit::Spade)];
+        let (first, second) = (game.players[lead].id, game.players[1 - lead].id);
+
+        play(&mut game, first, card(Rank::Ace, Suit::Spade));
+        let events = play(&mut game, second, card(Rank::Five, Suit::Spade));
+
+        assert!(matches!(events.last(), Some(Event::EndGame { p_id }) if *p_id == first));
+        assert_eq!(game.get_phase(), GamePhase::Ended);
+    }
+
+    #[test]
+    fn duplicate_table_waits_for_every_seat() {
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, ..RoomSettings::default() });
+        game.set_fixed_deal(standard_deck(), 1);
+        for _ in 0..2 {
+            game.add_player();
+        }
+        game.apply_action(Action::Ready { player_id : PlayerId(0), name : None });
+        game.apply_action(Action::Ready { player_id : PlayerId(1), name : None });
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+
+        let events = game.apply_action(Action::ForceStart { player_id : PlayerId(0) });
+        assert!(matches!(events.as_slice(), [Event::Error { .. }]));
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+
+        game.add_player();
+        game.apply_action(Action::Ready { player_id : PlayerId(2), name : None });
+        assert_eq!(game.get_phase(), GamePhase::Playing);
+    }
+
+    #[test]
+    fn bots_left_alone_never_start() {
+        let mut game = Game::new();
+        let Some(Event::PlayerAdded { p_id }) = game.add_player() else {
+            panic!("the table has room");
+        };
+        game.add_bot(BotProfile::default());
+        game.add_bot(BotProfile::default());
+
+        let events = game.remove_player(p_id);
+        assert!(!events.iter().any(|ev| matches!(ev, Event::StartGame)));
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+    }
+
+    #[test]
+    fn practice_rooms_keep_their_settings() {
+        let scenario = crate::game::tutorial::find("opening").expect("scenario exists");
+        let mut game = Game::with_settings(RoomSettings { max_players : scenario.players(), ..RoomSettings::default() });
+        game.add_player();
+        game.set_tutorial(scenario);
+
+        let settings = RoomSettings { max_players : 4, ..RoomSettings::default() };
+        let events = game.apply_action(Action::UpdateSettings { player_id : PlayerId(0), settings });
+        assert!(matches!(events.as_slice(), [Event::Error { .. }]));
+        assert_eq!(game.get_settings().max_players, scenario.players());
+    }
+
+    #[test]
+    fn no_rescue_finishes_the_trick_winner() {
+        let (game, [first, second, _], events) = ace_wins_the_trick(false, vec![card(Rank::Five, Suit::Spade), card(Rank::Seven, Suit::Heart)]);
+
+        assert!(events.iter().any(|ev| matches!(ev, Event::PlayerWon { player_id } if *player_id == first)));
+        assert!(!events.iter().any(|ev| matches!(ev, Event::SpecialEvent { .. })));
+        assert!(matches!(events.last(), Some(Event::NextTurn { player_id }) if *player_id == second));
+        assert!(game.players.iter().any(|p| p.id == first && p.finished));
+        assert_eq!(game.turn, second);
+    }
+
+    #[test]
+    fn rescue_hands_the_trick_winner_a_card() {
+        let (game, [first, second, _], events) = ace_wins_the_trick(true, vec![card(Rank::Five, Suit::Spade), card(Rank::Seven, Suit::Heart), card(Rank::Three, Suit::Heart)]);
+
+        assert!(events.iter().any(|ev| matches!(ev, Event::SpecialEvent { p_id, from, .. } if *p_id == first && *from == second)));
+        assert!(!events.iter().any(|ev| matches!(ev, Event::PlayerWon { .. })));
+        assert!(matches!(events.last(), Some(Event::NextTurn { player_id }) if *player_id == first));
+        assert_eq!(game.get_hand(first).map(|hand| hand.len()), Some(1));
+    }
+
+    #[test]
+    fn game_over_sums_up_the_game() {
+        let mut game = Game::with_settings(RoomSettings { max_players : 2, ..RoomSettings::default() });
+        game.add_player();
+        game.add_player();
+        game.start_game();
+        let deal = game.dealt.clone();
+
+        let lead = game.find_by_player_id(game.turn).expect("A♠ is dealt");
+        game.players[lead].hand = vec![card(Rank::Ace, Suit::Spade), card(Rank::Two, Suit::Heart)];
+        game.players[1 - lead].hand = vec![card(Rank::Three, Suit::Diamond)];
+        let (first, second) = (game.players[lead].id, game.players[1 - lead].id);
+
+        play(&mut game, first, card(Rank::Ace, Suit::Spade));
+        let events = play(&mut game, second, card(Rank::Three, Suit::Diamond));
+
+        let Some(Event::GameOver { summary }) = events.iter().find(|ev| matches!(ev, Event::GameOver { .. })) else {
+            panic!("the foul emptied the last hand");
+        };
+        assert_eq!(summary.finishing_order, vec![second]);
+        assert_eq!(summary.standings(), vec![format!("Player {}", second.0), format!("Player {}", first.0)]);
+        assert_eq!(summary.loser, first);
+        assert_eq!((summary.turns, summary.tricks_discarded), (2, 0));
+        assert_eq!(summary.deal, deal);
+        let stats = |p_id : PlayerId| summary.players.iter().find(|p| p.p_id == p_id).expect("every seat has stats");
+        assert_eq!((stats(second).fouls_given, stats(first).fouls_received), (1, 1));
+        assert_eq!(stats(first).final_hand.len(), 3);
+        assert!(stats(second).final_hand.is_empty());
+    }
+
+    /// two seats that both go out on the first trick , the table is in the post game lobby
+    fn ended_game() -> Game {
+        let rules = HouseRules { empty_hand_rescue : false, ..HouseRules::default() };
+        let mut game = Game::with_settings(RoomSettings { max_players : 2, rules, ..RoomSettings::default() });
+        game.add_player();
+        game.add_player();
+        game.start_game();
+
+        let lead = game.find_by_player_id(game.turn).expect("A♠ is dealt");
+        game.players[lead].hand = vec![card(Rank::Ace, Suit::Spade)];
+        game.players[1 - lead].hand = vec![card(Rank::Five, Suit::Spade)];
+        let (first, second) = (game.players[lead].id, game.players[1 - lead].id);
+        play(&mut game, first, card(Rank::Ace, Suit::Spade));
+        play(&mut game, second, card(Rank::Five, Suit::Spade));
+        assert_eq!(game.get_phase(), GamePhase::Ended);
+        game
+    }
+
+    #[test]
+    fn only_the_table_closes_the_post_game_lobby() {
+        let mut game = ended_game();
+        let (host, other) = (PlayerId(0), PlayerId(1));
+
+        assert!(game.apply_action(Action::EndGame { player_id : PlayerId(3) }).is_empty());
+        game.apply_action(Action::Rematch { player_id : other });
+        assert!(game.apply_action(Action::EndGame { player_id : other }).is_empty());
+        assert_eq!(game.get_phase(), GamePhase::Ended);
+
+        let events = game.apply_action(Action::EndGame { player_id : host });
+        assert!(matches!(events.as_slice(), [Event::ReturnToLobby]));
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+    }
+
+    #[test]
+    fn unready_and_leaving_in_the_lobby() {
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, ..RoomSettings::default() });
+        for _ in 0..3 {
+            game.add_player();
+        }
+        let ready = |game : &mut Game, i : u32| game.apply_action(Action::Ready { player_id : PlayerId(i), name : None });
+        ready(&mut game, 0);
+        ready(&mut game, 1);
+
+        let events = game.apply_action(Action::Unready { player_id : PlayerId(1) });
+        assert!(matches!(events.as_slice(), [Event::MarkUnready { p_id }] if *p_id == PlayerId(1)));
+        assert!(game.apply_action(Action::Unready { player_id : PlayerId(1) }).is_empty());
+        assert!(!ready(&mut game, 2).iter().any(|ev| matches!(ev, Event::StartGame)));
+
+        // the host leaving hands the table on to the next person seated
+        let events = game.apply_action(Action::LeaveTable { player_id : PlayerId(0) });
+        assert!(matches!(events.as_slice(), [Event::PlayerLeft { p_id }, Event::HostChanged { p_id : host }] if *p_id == PlayerId(0) && *host == PlayerId(1)));
+        let events = game.apply_action(Action::LeaveTable { player_id : PlayerId(1) });
+        assert!(matches!(events.as_slice(), [Event::PlayerLeft { .. }, Event::HostChanged { p_id }] if *p_id == PlayerId(2)));
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+
+        game.add_player();
+        ready(&mut game, 0);
+        assert_eq!(game.get_phase(), GamePhase::Playing);
+        assert!(game.apply_action(Action::Unready { player_id : PlayerId(0) }).is_empty());
+    }
+
+    #[test]
+    fn managed_tables_keep_the_host_out() {
+        let mut game = Game::with_settings(RoomSettings { max_players : 3, ..RoomSettings::default() });
+        game.set_managed();
+        for _ in 0..3 {
+            game.add_player();
+        }
+        let (host, rival) = (PlayerId(0), PlayerId(1));
+        game.apply_action(Action::Ready { player_id : host, name : None });
+
+        let refused = |events : Vec<Event>| matches!(events.as_slice(), [Event::Error { .. }]);
+        assert!(refused(game.apply_action(Action::KickPlayer { player_id : host, target : rival })));
+        assert!(refused(game.apply_action(Action::ForceStart { player_id : host })));
+        assert!(refused(game.apply_action(Action::AddBot { player_id : host, profile : BotProfile::default() })));
+        assert!(refused(game.apply_action(Action::UpdateSettings { player_id : host, settings : RoomSettings::default() })));
+        assert_eq!(game.get_seats().len(), 3);
+        assert_eq!(game.get_phase(), GamePhase::Waiting);
+        assert_eq!(game.get_settings().max_players, 3);
+    }
+
+    #[test]
+    fn duplicate_tables_keep_the_deal_and_settings_to_themselves() {
+        let mut game = Game::with_settings(RoomSettings { max_players : 2, ..RoomSettings::default() });
+        game.set_fixed_deal(standard_deck(), 0);
+        game.add_player();
+        game.add_player();
+
+        let hearts = RoomSettings { mode : GameMode::Hearts, max_players : 2, ..RoomSettings::default() };
+        let events = game.apply_action(Action::UpdateSettings { player_id : PlayerId(0), settings : hearts });
+        assert!(matches!(events.as_slice(), [Event::Error { .. }]));
+        assert_eq!(game.get_settings().mode, GameMode::Classic);
+
+        game.start_game();
+        let lead = game.find_by_player_id(game.turn).expect("A♠ is dealt");
+        game.players[lead].hand = vec![card(Rank::Ace, Suit::Spade)];
+        game.players[1 - lead].hand = vec![card(Rank::Five, Suit::Spade)];
+        let (first, second) = (game.players[lead].id, game.players[1 - lead].id);
+        play(&mut game, first, card(Rank::Ace, Suit::Spade));
+        let events = play(&mut game, second, card(Rank::Five, Suit::Spade));
+
+        let Some(Event::GameOver { summary }) = events.iter().find(|ev| matches!(ev, Event::GameOver { .. })) else {
+            panic!("both hands are empty");
+        };
+        assert!(summary.deal.is_empty());
+    }
+
+    fn team_game() -> Game {
+        let rules = HouseRules { empty_hand_rescue : false, ..HouseRules::default() };
+        let mut game = Game::with_settings(RoomSettings { max_players : TEAM_PLAYERS, team_mode : true, rules, ..RoomSettings::default() });
+        for _ in 0..TEAM_PLAYERS {
+            game.add_player();
+        }
+        game
+    }
+
+    #[test]
+    fn partners_sit_opposite() {
+        let mut game = team_game();
+        let seats = game.get_seats();
+        assert_eq!(game.get_teams(), Some(vec![vec![seats[0], seats[2]], vec![seats[1], seats[3]]]));
+
+        game.remove_player(seats[3]);
+        assert_eq!(game.get_teams(), None);
+        assert!(game.seating_problem(3).is_some());
+    }
+
+    #[test]
+    fn a_team_is_safe_once_both_partners_are_out() {
+        let mut game = team_game();
+        game.start_game();
+        let [a, b, c, d] = [0, 1, 2, 3].map(PlayerId);
+        let hands = [
+            vec![card(Rank::Ace, Suit::Spade), card(Rank::King, Suit::Diamond)],
+            vec![card(Rank::Five, Suit::Spade), card(Rank::Three, Suit::Diamond)],
+            vec![card(Rank::Nine, Suit::Spade)],
+            vec![card(Rank::Two, Suit::Spade), card(Rank::Four, Suit::Diamond), card(Rank::Six, Suit::Club)],
+        ];
+        for (player, hand) in game.players.iter_mut().zip(hands) {
+            player.hand = hand;
+        }
+        game.turn = a;
+
+        play(&mut game, a, card(Rank::Ace, Suit::Spade));
+        play(&mut game, b, card(Rank::Five, Suit::Spade));
+        play(&mut game, c, card(Rank::Nine, Suit::Spade));
+        let events = play(&mut game, d, card(Rank::Two, Suit::Spade));
+        // one partner out decides nothing
+        assert!(events.iter().any(|ev| matches!(ev, Event::PlayerWon { player_id } if *player_id == c)));
+        assert!(!events.iter().any(|ev| matches!(ev, Event::TeamSafe { .. })));
+
+        play(&mut game, a, card(Rank::King, Suit::Diamond));
+        play(&mut game, b, card(Rank::Three, Suit::Diamond));
+        let events = play(&mut game, d, card(Rank::Four, Suit::Diamond));
+        assert!(events.iter().any(|ev| matches!(ev, Event::TeamSafe { team : 0, players } if *players == vec![a, c])));
+        let Some(Event::GameOver { summary }) = events.iter().find(|ev| matches!(ev, Event::GameOver { .. })) else {
+            panic!("a team is out");
+        };
+        assert_eq!((summary.loser, summary.losing_team), (d, Some(1)));
+        assert_eq!(game.get_phase(), GamePhase::Ended);
+    }
+}
@@ -2,4 +2,18 @@ pub mod actions;
 pub mod event;
 pub mod players;
 pub mod card;
-pub mod logic;
\ No newline at end of file
+pub mod rng;
+pub mod logic;
+pub mod summary;
+pub mod settings;
+pub mod rules;
+pub mod hearts;
+pub mod callbreak;
+pub mod pile;
+pub mod trick;
+pub mod knowledge;
+pub mod view;
+pub mod crazy_eights;
+pub mod tournament;
+pub mod duplicate;
+pub mod tutorial;
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::game::card::{Card, shuffle};
+
+/// a face down stock to draw from and a face up discard pile
+#[derive(Clone)]
+pub struct DrawPile {
+    /// drawn from the back
+    stock : Vec<Card>,
+    /// top card last
+    discard : Vec<Card>,
+}
+
+impl DrawPile {
+    /// `stock` is used in the order given , shuffle it first
+    pub fn new(stock : Vec<Card>) -> Self {
+        Self { stock, discard : Vec::new() }
+    }
+
+    /// takes the top card of the stock , turning the discard pile over when the stock is empty
+    /// the second value is true when that reshuffle happened
+    pub fn draw(&mut self) -> (Option<Card>, bool) {
+        let mut reshuffled = false;
+        if self.stock.is_empty() && self.discard.len() > 1 {
+            // the top card stays where it is
+            let top = self.discard.pop().expect("discard has cards");
+            self.stock = std::mem::take(&mut self.discard);
+            shuffle(&mut self.stock);
+            self.discard.push(top);
+            reshuffled = true;
+        }
+        (self.stock.pop(), reshuffled)
+    }
+
+    pub fn discard(&mut self, card : Card) {
+        self.discard.push(card);
+    }
+
+    pub fn top(&self) -> Option<Card> {
+        self.discard.last().copied()
+    }
+
+    /// puts a card back into the middle of the stock
+    pub fn bury(&mut self, card : Card) {
+        let middle = self.stock.len() / 2;
+        self.stock.insert(middle, card);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::{Rank, Suit};
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    #[test]
+    fn draws_from_the_back_of_the_stock() {
+        let mut pile = DrawPile::new(vec![card(Rank::Two, Suit::Club), card(Rank::Three, Suit::Club)]);
+        assert_eq!(pile.draw(), (Some(card(Rank::Three, Suit::Club)), false));
+        assert_eq!(pile.draw(), (Some(card(Rank::Two, Suit::Club)), false));
+    }
+
+    #[test]
+    fn an_empty_stock_takes_the_discards_but_the_top_card() {
+        let mut pile = DrawPile::new(Vec::new());
+        let discards = [card(Rank::Four, Suit::Heart), card(Rank::Five, Suit::Heart), card(Rank::Six, Suit::Heart)];
+        for c in discards {
+            pile.discard(c);
+        }
+
+        let (first, reshuffled) = pile.draw();
+        assert!(reshuffled);
+        let (second, again) = pile.draw();
+        assert!(!again);
+
+        let mut drawn = vec![first.unwrap(), second.unwrap()];
+        drawn.sort_by_key(|c| c.rank);
+        assert_eq!(drawn, discards[..2].to_vec());
+        assert_eq!(pile.top(), Some(card(Rank::Six, Suit::Heart)));
+    }
+
+    #[test]
+    fn nothing_to_draw_when_only_the_top_card_is_left() {
+        let mut pile = DrawPile::new(Vec::new());
+        pile.discard(card(Rank::Ace, Suit::Spade));
+        assert_eq!(pile.draw(), (None, false));
+        assert_eq!(pile.top(), Some(card(Rank::Ace, Suit::Spade)));
+    }
+}
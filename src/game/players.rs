@@ -1,4 +1,5 @@
 use crate::game::card::Card;
+use crate::bot::profile::BotProfile;
 use std::hash::Hash;
 use serde::{Serialize, Deserialize};
 
@@ -20,24 +21,57 @@ pub struct PlayerId(pub u32);
 //     }
 // }
 
+const MAX_NAME_LEN : usize = 20;
+
+#[derive(Clone)]
 pub struct Players {
     pub id: PlayerId,
+    pub name : String,
     pub hand: Vec<Card>,
     pub ready : bool,
+    /// got rid of all cards , keeps the seat but is skipped for turns
+    pub finished : bool,
+    /// played by the server , how is up to the host
+    pub bot : Option<BotProfile>,
 }
 
 impl Players {
     pub fn new(id: PlayerId) -> Self {
         Self {
             id,
+            name : format!("Player {}", id.0),
             hand: Vec::new(),
             ready : false,
+            finished : false,
+            bot : None,
+        }
+    }
+
+    pub fn new_bot(id: PlayerId, profile : BotProfile) -> Self {
+        Self {
+            name : format!("Bot {}", id.0),
+            ready : true,
+            bot : Some(profile),
+            ..Self::new(id)
+        }
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.bot.is_some()
+    }
+
+    /// empty names are ignored , long ones are cut
+    pub fn set_name(&mut self, name : &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
         }
+        self.name = name.chars().take(MAX_NAME_LEN).collect();
     }
 
     pub fn remove_card(&mut self, card: &Card) -> Option<Card> {
         let idx = self.hand.iter().position(|c| c == card)?;
-        return Some(self.hand.swap_remove(idx));
+        Some(self.hand.swap_remove(idx))
         // if let Some(idx) = self.hand.iter().position(|c| c == card) {
         //     Some(self.hand.swap_remove(idx))
             
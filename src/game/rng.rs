@@ -0,0 +1,26 @@
+use std::cell::RefCell;
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+thread_local! {
+    static SEEDED : RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// everything random on this thread follows `seed` from now on , None goes back to the os rng
+/// deals , the rescue card and bot choices all draw from here so a seeded game plays out the same every time
+pub fn seed(seed : Option<u64>) {
+    SEEDED.with(|rng| *rng.borrow_mut() = seed.map(StdRng::seed_from_u64));
+}
+
+/// runs `f` with the seeded rng if this thread has one , the thread rng otherwise
+pub fn with<T>(f : impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    SEEDED.with(|seeded| match seeded.borrow_mut().as_mut() {
+        Some(rng) => f(rng),
+        None => f(&mut rand::rng()),
+    })
+}
+
+/// puts `rng` in as this thread's rng and hands back the one it replaces
+/// lets an owner of its own rng lend it to everything random for a while , then take it back
+pub fn swap(rng : Option<StdRng>) -> Option<StdRng> {
+    SEEDED.with(|seeded| seeded.replace(rng))
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::game::card::{Card, Rank, Suit};
+
+/// who picks up the trick when someone fouls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FoulTo {
+    /// whoever played the highest card so far
+    Highest,
+    /// whoever led the trick
+    Leader,
+}
+
+/// the table rules a room plays by , the defaults are the classic game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HouseRules {
+    /// the first card of a game must be the ace of spades , otherwise its holder leads anything
+    pub ace_of_spades_opens : bool,
+    pub foul_to : FoulTo,
+    /// a trick winner with an empty hand takes a random card from the next player
+    pub empty_hand_rescue : bool,
+    /// aces rank below twos
+    pub ace_low : bool,
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        Self {
+            ace_of_spades_opens : true,
+            foul_to : FoulTo::Highest,
+            empty_hand_rescue : true,
+            ace_low : false,
+        }
+    }
+}
+
+impl HouseRules {
+    /// reads rules from a `.json` file , anything else is parsed as toml
+    pub fn load(path : &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let rules = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&text).map_err(|e| e.to_string())
+        };
+        rules.map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// strength of a card under these rules , higher beats lower
+    pub fn rank_value(&self, rank : Rank) -> u8 {
+        match rank {
+            Rank::Ace if self.ace_low => 1,
+            rank => rank as u8,
+        }
+    }
+
+    /// whether `card` may start a game
+    pub fn can_open(&self, card : &Card) -> bool {
+        !self.ace_of_spades_opens || (card.rank == Rank::Ace && card.suit == Suit::Spade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// writes `text` to a file of its own in the temp dir
+    fn rules_file(name : &str, text : &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("house_rules_{}_{name}", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_toml_and_json_with_defaults_for_the_rest() {
+        let toml = rules_file("rules.toml", "ace_low = true\nfoul_to = \"Leader\"\n");
+        let rules = HouseRules::load(&toml).unwrap();
+        assert_eq!(rules, HouseRules { ace_low : true, foul_to : FoulTo::Leader, ..HouseRules::default() });
+
+        let json = rules_file("rules.json", r#"{"empty_hand_rescue": false}"#);
+        let rules = HouseRules::load(&json).unwrap();
+        assert_eq!(rules, HouseRules { empty_hand_rescue : false, ..HouseRules::default() });
+
+        let _ = std::fs::remove_file(toml);
+        let _ = std::fs::remove_file(json);
+    }
+
+    #[test]
+    fn bad_rules_name_the_file() {
+        let bad = rules_file("bad.toml", "foul_to = \"Nobody\"\n");
+        let err = HouseRules::load(&bad).unwrap_err();
+        assert!(err.contains(&bad.display().to_string()));
+        let _ = std::fs::remove_file(bad);
+
+        assert!(HouseRules::load(Path::new("/no/such/rules.toml")).is_err());
+    }
+
+    #[test]
+    fn ace_low_and_free_opening() {
+        let rules = HouseRules { ace_low : true, ace_of_spades_opens : false, ..HouseRules::default() };
+        assert!(rules.rank_value(Rank::Ace) < rules.rank_value(Rank::Two));
+        assert!(rules.can_open(&Card { rank : Rank::Two, suit : Suit::Heart }));
+
+        let classic = HouseRules::default();
+        assert!(classic.rank_value(Rank::Ace) > classic.rank_value(Rank::King));
+        assert!(!classic.can_open(&Card { rank : Rank::King, suit : Suit::Spade }));
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Serialize, Deserialize};
+use crate::game::rules::HouseRules;
+
+pub const MIN_PLAYERS : usize = 2;
+pub const MAX_PLAYERS : usize = 4;
+/// team games are always two against two
+pub const TEAM_PLAYERS : usize = 4;
+
+/// hearts needs at least three at the table
+pub const HEARTS_MIN_PLAYERS : usize = 3;
+pub const CALLBREAK_PLAYERS : usize = 4;
+
+const MIN_TURN_SECS : u64 = 5;
+const MAX_TURN_SECS : u64 = 300;
+/// bots think while the table waits , keep it short
+const MAX_SEARCH_MILLIS : u64 = 5000;
+const DEFAULT_SEARCH_MILLIS : u64 = 300;
+const MAX_SEARCH_ITERATIONS : u32 = 20000;
+
+/// which game the room plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// shed your cards , the last one holding any loses
+    #[default]
+    Classic,
+    Hearts,
+    /// bids , spades trump , five deals
+    Callbreak,
+    /// match suit or rank , eights are wild , draw when stuck
+    CrazyEights,
+}
+
+/// how long a searching bot may think about each move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchBudget {
+    /// simulated games per move
+    Iterations(u32),
+    /// wall clock time per move
+    Millis(u64),
+}
+
+/// options the host can change while the table is waiting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoomSettings {
+    pub max_players : usize,
+    /// seconds a player has for a move , None means no limit
+    pub turn_timer_secs : Option<u64>,
+    /// private rooms are never listed publicly
+    pub private : bool,
+    /// partners opposite each other , the team still holding cards loses
+    pub team_mode : bool,
+    pub rules : HouseRules,
+    pub mode : GameMode,
+    /// how long bots at `Search` difficulty think about a move
+    pub bot_search : SearchBudget,
+    /// players may ask which card to play
+    pub hints : bool,
+}
+
+impl Default for RoomSettings {
+    fn default() -> Self {
+        Self {
+            max_players : MAX_PLAYERS,
+            turn_timer_secs : None,
+            private : false,
+            team_mode : false,
+            rules : HouseRules::default(),
+            mode : GameMode::Classic,
+            bot_search : SearchBudget::Millis(DEFAULT_SEARCH_MILLIS),
+            hints : true,
+        }
+    }
+}
+
+impl RoomSettings {
+    /// checks the settings make sense for a table with `seated` players
+    pub fn validate(&self, seated : usize) -> Result<(), String> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.max_players) {
+            return Err(format!("max players must be between {MIN_PLAYERS} and {MAX_PLAYERS}"));
+        }
+        if self.max_players < seated {
+            return Err(format!("{seated} players are already seated"));
+        }
+        if self.team_mode && self.max_players != TEAM_PLAYERS {
+            return Err(format!("team games need {TEAM_PLAYERS} players"));
+        }
+        if self.team_mode && self.mode != GameMode::Classic {
+            return Err("teams only play the classic game".into());
+        }
+        if self.mode == GameMode::Hearts && self.max_players < HEARTS_MIN_PLAYERS {
+            return Err(format!("hearts needs {HEARTS_MIN_PLAYERS} or more players"));
+        }
+        if self.mode == GameMode::Callbreak && self.max_players != CALLBREAK_PLAYERS {
+            return Err(format!("callbreak needs {CALLBREAK_PLAYERS} players"));
+        }
+        if let Some(secs) = self.turn_timer_secs
+            && !(MIN_TURN_SECS..=MAX_TURN_SECS).contains(&secs) {
+            return Err(format!("turn timer must be between {MIN_TURN_SECS} and {MAX_TURN_SECS} seconds"));
+        }
+        match self.bot_search {
+            SearchBudget::Iterations(n) if !(1..=MAX_SEARCH_ITERATIONS).contains(&n) => {
+                return Err(format!("bot search must be between 1 and {MAX_SEARCH_ITERATIONS} iterations"));
+            }
+            SearchBudget::Millis(ms) if !(1..=MAX_SEARCH_MILLIS).contains(&ms) => {
+                return Err(format!("bot search must be between 1 and {MAX_SEARCH_MILLIS} ms"));
+            }
+            SearchBudget::Iterations(_) | SearchBudget::Millis(_) => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::game::card::Card;
+use crate::game::players::PlayerId;
+
+/// what a single player did over the whole game
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerStats {
+    pub p_id : PlayerId,
+    /// as seated when the game ended , it stays here after they leave
+    pub name : String,
+    pub fouls_given : u32,
+    pub fouls_received : u32,
+    pub final_hand : Vec<Card>,
+}
+
+/// sent once with GameOver so clients can draw a results screen
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSummary {
+    /// players in the order they got rid of their cards, loser is not in here
+    pub finishing_order : Vec<PlayerId>,
+    pub loser : PlayerId,
+    /// one entry per seat , in seat order
+    pub players : Vec<PlayerStats>,
+    pub tricks_discarded : u32,
+    /// number of cards successfully played
+    pub turns : u32,
+    /// deck order the game was dealt from , cards are dealt from the back
+    pub deal : Vec<Card>,
+    /// partners in team games , empty otherwise
+    pub teams : Vec<Vec<PlayerId>>,
+    /// index into `teams` of the team left holding cards
+    pub losing_team : Option<usize>,
+    /// points per seat in games that keep score , empty otherwise
+    pub scores : Vec<f64>,
+}
+
+impl GameSummary {
+    /// names from the first one out to the loser
+    pub fn standings(&self) -> Vec<String> {
+        let name = |p_id : &PlayerId| self.players.iter().find(|p| p.p_id == *p_id).map(|p| p.name.clone()).unwrap_or_default();
+        self.finishing_order.iter().chain(std::iter::once(&self.loser)).map(name).collect()
+    }
+
+    /// finishing place of every seat , 0 went out first , in seat order
+    pub fn places(&self) -> Vec<usize> {
+        let order : Vec<PlayerId> = self.finishing_order.iter().copied().chain(std::iter::once(self.loser)).collect();
+        self.players.iter().map(|p| order.iter().position(|&id| id == p.p_id).unwrap_or(order.len())).collect()
+    }
+}
+
+/// counters collected while a game is being played
+#[derive(Default, Clone)]
+pub struct GameStats {
+    seats : Vec<PlayerId>,
+    finishing_order : Vec<PlayerId>,
+    fouls_given : HashMap<PlayerId, u32>,
+    fouls_received : HashMap<PlayerId, u32>,
+    tricks_discarded : u32,
+    turns : u32,
+}
+
+impl GameStats {
+    pub fn new(seats : Vec<PlayerId>) -> Self {
+        Self {
+            seats,
+            ..Default::default()
+        }
+    }
+
+    pub fn record_turn(&mut self) {
+        self.turns += 1;
+    }
+
+    pub fn record_discard(&mut self) {
+        self.tricks_discarded += 1;
+    }
+
+    pub fn record_foul(&mut self, from : PlayerId, to : PlayerId) {
+        *self.fouls_given.entry(from).or_insert(0) += 1;
+        *self.fouls_received.entry(to).or_insert(0) += 1;
+    }
+
+    pub fn record_finish(&mut self, p_id : PlayerId) {
+        if !self.finishing_order.contains(&p_id) {
+            self.finishing_order.push(p_id);
+        }
+    }
+
+    /// builds the summary , `hand_of` returns the cards a seat is still holding
+    pub fn summary(&self, loser : PlayerId, hand_of : impl Fn(PlayerId) -> Vec<Card>) -> GameSummary {
+        let players = self.seats
+            .iter()
+            .map(|&p_id| PlayerStats {
+                p_id,
+                name : String::new(),
+                fouls_given : self.fouls_given.get(&p_id).copied().unwrap_or(0),
+                fouls_received : self.fouls_received.get(&p_id).copied().unwrap_or(0),
+                final_hand : hand_of(p_id),
+            })
+            .collect();
+
+        GameSummary {
+            finishing_order : self.finishing_order.clone(),
+            loser,
+            players,
+            tricks_discarded : self.tricks_discarded,
+            turns : self.turns,
+            deal : Vec::new(),
+            teams : Vec::new(),
+            losing_team : None,
+            scores : Vec::new(),
+        }
+    }
+}
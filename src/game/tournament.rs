@@ -0,0 +1,254 @@
+use serde::Serialize;
+use crate::game::settings::{MAX_PLAYERS, MIN_PLAYERS};
+
+/// most qualifying rounds a tournament can have before the final
+pub const MAX_ROUNDS : u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Stage {
+    Registering,
+    /// qualifying round , counted from 1
+    Round(u32),
+    Final,
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Entrant {
+    pub name : String,
+    pub points : u32,
+    pub games_played : u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Table {
+    pub players : Vec<String>,
+    /// best first , loser last , None while the game is running
+    pub result : Option<Vec<String>>,
+}
+
+/// seats players over several tables for a fixed number of rounds , then one final table
+#[derive(Debug, Clone, Serialize)]
+pub struct Tournament {
+    pub name : String,
+    pub table_size : usize,
+    pub rounds : u32,
+    pub stage : Stage,
+    pub entrants : Vec<Entrant>,
+    /// tables of the stage being played
+    pub tables : Vec<Table>,
+    /// tables of every finished stage , oldest first
+    pub history : Vec<Vec<Table>>,
+    /// final table order once finished
+    pub winners : Vec<String>,
+}
+
+impl Tournament {
+    pub fn new(name : String, table_size : usize, rounds : u32) -> Result<Self, String> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&table_size) {
+            return Err(format!("table size must be between {MIN_PLAYERS} and {MAX_PLAYERS}"));
+        }
+        if rounds > MAX_ROUNDS {
+            return Err(format!("at most {MAX_ROUNDS} rounds"));
+        }
+
+        Ok(Self {
+            name,
+            table_size,
+            rounds,
+            stage : Stage::Registering,
+            entrants : Vec::new(),
+            tables : Vec::new(),
+            history : Vec::new(),
+            winners : Vec::new(),
+        })
+    }
+
+    pub fn register(&mut self, name : &str) -> Result<(), String> {
+        let name = name.trim();
+        if self.stage != Stage::Registering {
+            return Err("registration is closed".into());
+        }
+        if name.is_empty() {
+            return Err("name can not be empty".into());
+        }
+        if self.entrants.iter().any(|e| e.name == name) {
+            return Err(format!("{name} is already registered"));
+        }
+
+        self.entrants.push(Entrant { name : name.to_string(), points : 0, games_played : 0 });
+        Ok(())
+    }
+
+    /// closes registration and seats the first stage , returns the new tables
+    pub fn start(&mut self) -> Result<&[Table], String> {
+        if self.stage != Stage::Registering {
+            return Err("tournament already started".into());
+        }
+        if self.entrants.len() < MIN_PLAYERS {
+            return Err(format!("at least {MIN_PLAYERS} players are needed"));
+        }
+
+        // everyone fits at one table , no point in qualifying rounds
+        if self.rounds == 0 || self.entrants.len() <= self.table_size {
+            self.seat_final();
+        } else {
+            self.seat_round(1);
+        }
+        Ok(&self.tables)
+    }
+
+    /// stores the finishing order of a table , returns true when a new stage was seated
+    pub fn record_result(&mut self, table : usize, order : Vec<String>) -> bool {
+        let Some(t) = self.tables.get_mut(table) else {
+            return false;
+        };
+        if t.result.is_some() {
+            return false;
+        }
+
+        // finishing earlier is worth more , the loser gets nothing
+        let n = order.len() as u32;
+        for (pos, name) in order.iter().enumerate() {
+            if let Some(e) = self.entrants.iter_mut().find(|e| &e.name == name) {
+                e.points += n - 1 - pos as u32;
+                e.games_played += 1;
+            }
+        }
+        t.result = Some(order);
+
+        if self.tables.iter().any(|t| t.result.is_none()) {
+            return false;
+        }
+
+        match self.stage {
+            Stage::Round(r) if r < self.rounds => self.seat_round(r + 1),
+            Stage::Round(_) => self.seat_final(),
+            Stage::Final => {
+                self.winners = self.tables[0].result.clone().unwrap_or_default();
+                self.history.push(std::mem::take(&mut self.tables));
+                self.stage = Stage::Finished;
+                return false;
+            }
+            Stage::Registering | Stage::Finished => return false,
+        }
+        true
+    }
+
+    /// entrants best first , ties go to the earlier registration
+    pub fn standings(&self) -> Vec<Entrant> {
+        let mut standings = self.entrants.clone();
+        standings.sort_by_key(|e| std::cmp::Reverse(e.points));
+        standings
+    }
+
+    fn seat_round(&mut self, round : u32) {
+        let names : Vec<String> = self.standings().into_iter().map(|e| e.name).collect();
+        let mut table_count = names.len().div_ceil(self.table_size);
+        // nobody should end up alone at a table
+        while table_count > 1 && names.len() / table_count < MIN_PLAYERS {
+            table_count -= 1;
+        }
+
+        // snake order so every table gets a mix of leaders and chasers
+        let mut tables = vec![Vec::new(); table_count];
+        for (i, name) in names.into_iter().enumerate() {
+            let lap = i / table_count;
+            let idx = if lap.is_multiple_of(2) { i % table_count } else { table_count - 1 - i % table_count };
+            tables[idx].push(name);
+        }
+
+        self.next_stage(Stage::Round(round), tables);
+    }
+
+    fn seat_final(&mut self) {
+        let finalists = self.standings().into_iter().take(self.table_size).map(|e| e.name).collect();
+        self.next_stage(Stage::Final, vec![finalists]);
+    }
+
+    fn next_stage(&mut self, stage : Stage, tables : Vec<Vec<String>>) {
+        if !self.tables.is_empty() {
+            self.history.push(std::mem::take(&mut self.tables));
+        }
+        self.stage = stage;
+        self.tables = tables.into_iter().map(|players| Table { players, result : None }).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(table_size : usize, rounds : u32, names : &[&str]) -> Tournament {
+        let mut t = Tournament::new("cup".into(), table_size, rounds).unwrap();
+        for name in names {
+            t.register(name).unwrap();
+        }
+        t
+    }
+
+    fn seated(t : &Tournament) -> Vec<Vec<&str>> {
+        t.tables.iter().map(|table| table.players.iter().map(String::as_str).collect()).collect()
+    }
+
+    fn order(names : &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn points(t : &Tournament, name : &str) -> u32 {
+        t.entrants.iter().find(|e| e.name == name).map_or(0, |e| e.points)
+    }
+
+    #[test]
+    fn rounds_are_seated_in_snake_order() {
+        let mut t = tournament(3, 2, &["a", "b", "c", "d", "e", "f", "g"]);
+        t.start().unwrap();
+
+        assert_eq!(t.stage, Stage::Round(1));
+        assert_eq!(seated(&t), vec![vec!["a", "f", "g"], vec!["b", "e"], vec!["c", "d"]]);
+    }
+
+    #[test]
+    fn one_table_goes_straight_to_the_final() {
+        let mut t = tournament(4, 3, &["a", "b", "c"]);
+        t.start().unwrap();
+
+        assert_eq!(t.stage, Stage::Final);
+        assert_eq!(seated(&t), vec![vec!["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn finishing_earlier_is_worth_more() {
+        let mut t = tournament(3, 1, &["a", "b", "c", "d", "e", "f"]);
+        t.start().unwrap();
+        assert_eq!(seated(&t), vec![vec!["a", "d", "e"], vec!["b", "c", "f"]]);
+
+        assert!(!t.record_result(0, order(&["e", "a", "d"])));
+        assert_eq!((points(&t, "e"), points(&t, "a"), points(&t, "d")), (2, 1, 0));
+        // a table only counts once
+        assert!(!t.record_result(0, order(&["d", "a", "e"])));
+        assert_eq!(points(&t, "d"), 0);
+
+        assert!(t.record_result(1, order(&["c", "f", "b"])));
+        assert_eq!(t.stage, Stage::Final);
+        // ties go to the earlier registration , c before e and a before f
+        assert_eq!(seated(&t), vec![vec!["c", "e", "a"]]);
+        assert_eq!(t.history.len(), 1);
+
+        assert!(!t.record_result(0, order(&["a", "c", "e"])));
+        assert_eq!(t.stage, Stage::Finished);
+        assert_eq!(t.winners, order(&["a", "c", "e"]));
+        assert_eq!((points(&t, "a"), points(&t, "c"), points(&t, "e")), (3, 3, 2));
+        assert!(t.entrants.iter().all(|e| e.games_played == if ["a", "c", "e"].contains(&e.name.as_str()) { 2 } else { 1 }));
+    }
+
+    #[test]
+    fn registration_closes_at_the_start() {
+        let mut t = tournament(2, 0, &["a", "b"]);
+        assert!(t.register("a").is_err());
+        assert!(t.register("  ").is_err());
+        t.start().unwrap();
+        assert!(t.register("c").is_err());
+        assert!(t.start().is_err());
+    }
+}
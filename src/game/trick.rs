@@ -0,0 +1,143 @@
+use crate::game::card::{Card, Rank, Suit};
+use crate::game::players::PlayerId;
+
+/// one trick , the cards played in order by `size` players with an optional trump suit
+#[derive(Debug, Clone, Default)]
+pub struct Trick {
+    plays : Vec<(Card, PlayerId)>,
+    trump : Option<Suit>,
+    size : usize,
+}
+
+impl Trick {
+    pub fn new(size : usize, trump : Option<Suit>) -> Self {
+        Self { plays : Vec::new(), trump, size }
+    }
+
+    pub fn led_suit(&self) -> Option<Suit> {
+        self.plays.first().map(|(c, _)| c.suit)
+    }
+
+    pub fn plays(&self) -> &[(Card, PlayerId)] {
+        &self.plays
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plays.is_empty()
+    }
+
+    /// every player has played to it
+    pub fn is_complete(&self) -> bool {
+        self.size > 0 && self.plays.len() >= self.size
+    }
+
+    pub fn play(&mut self, card : Card, p_id : PlayerId) {
+        self.plays.push((card, p_id));
+    }
+
+    /// the play taking the trick so far , ranks in their natural order
+    pub fn winner(&self) -> Option<(Card, PlayerId)> {
+        self.winner_by(|rank| rank as u8)
+    }
+
+    /// highest trump if any was played , otherwise highest of the led suit , `value` ranks the cards
+    pub fn winner_by(&self, value : impl Fn(Rank) -> u8) -> Option<(Card, PlayerId)> {
+        let led = self.led_suit()?;
+        let suit = match self.trump {
+            Some(trump) if self.plays.iter().any(|(c, _)| c.suit == trump) => trump,
+            _ => led,
+        };
+        self.plays.iter().filter(|(c, _)| c.suit == suit).max_by_key(|(c, _)| value(c.rank)).copied()
+    }
+
+    /// true when `hand` holds a card of the led suit
+    pub fn can_follow(&self, hand : &[Card]) -> bool {
+        self.led_suit().is_some_and(|led| hand.iter().any(|c| c.suit == led))
+    }
+
+    /// cards of `hand` that follow suit , the whole hand when leading or void in the led suit
+    pub fn follow_cards(&self, hand : &[Card]) -> Vec<Card> {
+        if let Some(led) = self.led_suit() {
+            let follow : Vec<Card> = hand.iter().copied().filter(|c| c.suit == led).collect();
+            if !follow.is_empty() {
+                return follow;
+            }
+        }
+        hand.to_vec()
+    }
+
+    /// whether `card` may be played from `hand` , it has to be held and follow suit if possible
+    pub fn is_legal(&self, card : &Card, hand : &[Card]) -> bool {
+        hand.contains(card) && self.follow_cards(hand).contains(card)
+    }
+
+    /// clears the trick for the next one , returns what was played
+    pub fn take(&mut self) -> Vec<(Card, PlayerId)> {
+        std::mem::take(&mut self.plays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank : Rank, suit : Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    fn trick(trump : Option<Suit>, cards : &[Card]) -> Trick {
+        let mut trick = Trick::new(4, trump);
+        for (i, c) in cards.iter().enumerate() {
+            trick.play(*c, PlayerId(i as u32));
+        }
+        trick
+    }
+
+    #[test]
+    fn highest_of_the_led_suit_wins_without_trump() {
+        let trick = trick(None, &[card(Rank::Five, Suit::Heart), card(Rank::Ace, Suit::Spade), card(Rank::Jack, Suit::Heart)]);
+        assert_eq!(trick.winner(), Some((card(Rank::Jack, Suit::Heart), PlayerId(2))));
+        assert_eq!(Trick::new(4, None).winner(), None);
+    }
+
+    #[test]
+    fn any_trump_beats_the_led_suit() {
+        let trick = trick(Some(Suit::Spade), &[card(Rank::Ace, Suit::Heart), card(Rank::Two, Suit::Spade), card(Rank::King, Suit::Heart), card(Rank::Three, Suit::Spade)]);
+        assert_eq!(trick.winner(), Some((card(Rank::Three, Suit::Spade), PlayerId(3))));
+    }
+
+    #[test]
+    fn winner_by_uses_the_given_ranking() {
+        let trick = trick(None, &[card(Rank::Ace, Suit::Club), card(Rank::King, Suit::Club)]);
+        let ace_low = |rank : Rank| if rank == Rank::Ace { 1 } else { rank as u8 };
+        assert_eq!(trick.winner_by(ace_low), Some((card(Rank::King, Suit::Club), PlayerId(1))));
+        assert_eq!(trick.winner(), Some((card(Rank::Ace, Suit::Club), PlayerId(0))));
+    }
+
+    #[test]
+    fn legal_cards_follow_suit_when_they_can() {
+        let hand = [card(Rank::Two, Suit::Heart), card(Rank::Nine, Suit::Club)];
+        let led_club = trick(None, &[card(Rank::Ten, Suit::Club)]);
+        assert!(led_club.is_legal(&hand[1], &hand));
+        assert!(!led_club.is_legal(&hand[0], &hand));
+        assert!(!led_club.is_legal(&card(Rank::Ace, Suit::Club), &hand));
+
+        // void in the led suit or leading , anything held goes
+        let led_diamond = trick(None, &[card(Rank::Ten, Suit::Diamond)]);
+        assert!(led_diamond.is_legal(&hand[0], &hand));
+        assert!(Trick::new(4, None).is_legal(&hand[0], &hand));
+    }
+
+    #[test]
+    fn complete_once_everyone_played() {
+        let mut trick = trick(None, &[card(Rank::Two, Suit::Club), card(Rank::Three, Suit::Club), card(Rank::Four, Suit::Club)]);
+        assert!(!trick.is_complete());
+        trick.play(card(Rank::Five, Suit::Club), PlayerId(3));
+        assert!(trick.is_complete());
+
+        assert_eq!(trick.take().len(), 4);
+        assert!(trick.is_empty() && !trick.is_complete());
+        // the default trick has no size and is never complete
+        assert!(!Trick::default().is_complete());
+    }
+}
@@ -0,0 +1,350 @@
+use serde::Serialize;
+use crate::game::card::Card;
+use crate::game::event::Event;
+use crate::game::players::PlayerId;
+
+/// one move the learner is asked to make
+#[derive(Debug)]
+pub struct Step {
+    pub expect : &'static str,
+    pub prompt : &'static str,
+    /// what is wrong with particular other cards
+    pub mistakes : &'static [(&'static str, &'static str)],
+    /// for any other card
+    pub otherwise : &'static str,
+}
+
+/// a preset deal showing one rule , played against bots
+#[derive(Debug)]
+pub struct Scenario {
+    pub id : &'static str,
+    pub title : &'static str,
+    pub intro : &'static str,
+    /// hands in play order starting with the learner , like `AS KH 7C`
+    pub hands : &'static [&'static str],
+    /// seats after the learner of the player who leads , when nobody holds A♠
+    pub leader : usize,
+    pub steps : &'static [Step],
+    pub done : &'static str,
+}
+
+/// every scenario is written for three players and the default house rules
+pub const SCENARIOS : &[Scenario] = &[
+    Scenario {
+        id : "opening",
+        title : "The ace of spades opens",
+        intro : "Every game starts with A♠ , whoever holds it plays first.",
+        hands : &["AS 7H 3D", "5S 9H 4D", "8S 2H KD"],
+        leader : 0,
+        steps : &[
+            Step {
+                expect : "AS",
+                prompt : "You hold A♠ , so you start. Play A♠.",
+                mistakes : &[],
+                otherwise : "Nothing but A♠ may open the game.",
+            },
+        ],
+        done : "Everyone followed ♠ so the trick was thrown away , and A♠ was the highest card so you lead the next one.",
+    },
+    Scenario {
+        id : "follow",
+        title : "Following suit",
+        intro : "While you hold the suit that was led you have to play it.",
+        hands : &["KH 4H 9C", "8H 2C", "6H JC"],
+        leader : 2,
+        steps : &[
+            Step {
+                expect : "4H",
+                prompt : "6♥ was led and you hold hearts , so you must follow ♥. Play 4♥ , it stays under 6♥.",
+                mistakes : &[
+                    ("9C", "You still hold hearts , so a club isn't allowed here. Only once you run out of the led suit may you play something else."),
+                    ("KH", "K♥ is allowed but it tops the trick. If someone after you is out of ♥ and fouls , the whole trick goes to the highest card , yours. Stay under 6♥ with 4♥."),
+                ],
+                otherwise : "Follow ♥ with 4♥.",
+            },
+            Step {
+                expect : "9C",
+                prompt : "Clubs were led this time. 9♣ is your only club , so it is the only card you may play.",
+                mistakes : &[
+                    ("KH", "You hold a club , so you must follow ♣ with 9♣."),
+                ],
+                otherwise : "Follow ♣ with 9♣.",
+            },
+        ],
+        done : "While you hold the led suit you must play it , whatever else is in your hand.",
+    },
+    Scenario {
+        id : "foul",
+        title : "Fouls",
+        intro : "Out of the led suit you may play anything , and the trick goes to whoever is on top.",
+        hands : &["AD QC 3C", "5H 9S", "TH 6S"],
+        leader : 1,
+        steps : &[
+            Step {
+                expect : "AD",
+                prompt : "♥ was led and you have none , so any card is allowed : that is a foul. The whole trick then goes to the player of the highest ♥ , so dump your worst card , A♦.",
+                mistakes : &[
+                    ("QC", "Q♣ is allowed , but a foul is the moment to get rid of your highest card , A♦."),
+                    ("3C", "Low cards are the ones worth keeping. Dump A♦ instead."),
+                ],
+                otherwise : "Foul with A♦.",
+            },
+            Step {
+                expect : "QC",
+                prompt : "The player who picked up the trick leads ♥ again and you are still out of hearts. Foul again with your highest card , Q♣.",
+                mistakes : &[
+                    ("3C", "3♣ is allowed , but Q♣ is the card you want to be rid of."),
+                ],
+                otherwise : "Foul with Q♣.",
+            },
+        ],
+        done : "Both fouls sent the trick to the player on top , and you are down to your last card.",
+    },
+    Scenario {
+        id : "discard",
+        title : "Discarding a trick",
+        intro : "A trick that goes all the way round without a foul is thrown away , and its highest card leads next.",
+        hands : &["9S 2S 4D", "3S 8D", "5S JD"],
+        leader : 1,
+        steps : &[
+            Step {
+                expect : "9S",
+                prompt : "Everyone followed ♠ and you play last , so nobody can foul : the trick will be thrown away. That makes it safe to get rid of a high card , play 9♠.",
+                mistakes : &[
+                    ("2S", "2♠ is allowed , but the trick is thrown away whatever you play. Use the free moment to get rid of 9♠."),
+                    ("4D", "You hold spades , so you have to follow ♠."),
+                ],
+                otherwise : "Play 9♠.",
+            },
+            Step {
+                expect : "4D",
+                prompt : "Your 9♠ was the highest card of the thrown away trick , so you lead. In this practice deal the others are out of ♠ (in a real game you work that out from what was played) , so lead ♦ with 4♦.",
+                mistakes : &[
+                    ("2S", "Both others are out of ♠. Lead it and the next player fouls , handing the trick to you."),
+                ],
+                otherwise : "Lead 4♦.",
+            },
+        ],
+        done : "A trick everyone follows is thrown away , and the player of its highest card leads next.",
+    },
+    Scenario {
+        id : "rescue",
+        title : "The empty hand rescue",
+        intro : "Winning a trick with your last card doesn't get you out : you take a card from the next player and lead.",
+        hands : &["KS", "5S 7H", "9S 8H"],
+        leader : 1,
+        steps : &[
+            Step {
+                expect : "KS",
+                prompt : "K♠ is your last card and it will top this trick. Play it and see what happens.",
+                mistakes : &[],
+                otherwise : "Play K♠.",
+            },
+            Step {
+                expect : "7H",
+                prompt : "That was the rescue : the highest card of a thrown away trick leads next , and with nothing left to lead you took a random card from the next player. Lead 7♥ now.",
+                mistakes : &[],
+                otherwise : "Lead 7♥.",
+            },
+        ],
+        done : "Your 7♥ lost to 8♥ , so this time your empty hand took you out. To go out your last card must not win its trick.",
+    },
+];
+
+pub fn find(id : &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|s| s.id == id)
+}
+
+fn card(short : &str) -> Result<Card, String> {
+    short.parse()
+}
+
+impl Scenario {
+    /// the deal , cards that don't parse are left out so `validate` first
+    pub fn hands(&self) -> Vec<Vec<Card>> {
+        self.hands.iter().map(|hand| hand.split_whitespace().filter_map(|c| card(c).ok()).collect()).collect()
+    }
+
+    /// every card parses and is dealt once , the leader sits at the table and the asked for cards are in the deal
+    pub fn validate(&self) -> Result<(), String> {
+        if self.players() < 2 || self.leader >= self.players() {
+            return Err(format!("scenario {} needs two hands and a leader among them", self.id));
+        }
+        let mut dealt = vec![];
+        for short in self.hands.iter().flat_map(|hand| hand.split_whitespace()) {
+            let c = card(short).map_err(|e| format!("scenario {} : {e}", self.id))?;
+            if dealt.contains(&c) {
+                return Err(format!("scenario {} deals {short} twice", self.id));
+            }
+            dealt.push(c);
+        }
+        for short in self.steps.iter().flat_map(|step| std::iter::once(step.expect).chain(step.mistakes.iter().map(|(c, _)| *c))) {
+            let c = card(short).map_err(|e| format!("scenario {} : {e}", self.id))?;
+            if !dealt.contains(&c) {
+                return Err(format!("scenario {} asks about {short} which isn't dealt", self.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn players(&self) -> usize {
+        self.hands.len()
+    }
+}
+
+/// what a practice room lists about a scenario
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioInfo {
+    pub id : &'static str,
+    pub title : &'static str,
+    pub intro : &'static str,
+    pub players : usize,
+    pub steps : usize,
+}
+
+impl From<&Scenario> for ScenarioInfo {
+    fn from(s : &Scenario) -> Self {
+        Self { id : s.id, title : s.title, intro : s.intro, players : s.players(), steps : s.steps.len() }
+    }
+}
+
+/// how far the learner got through a scenario
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    scenario : &'static Scenario,
+    learner : Option<PlayerId>,
+    step : usize,
+    done : bool,
+}
+
+impl Tutorial {
+    pub fn new(scenario : &'static Scenario) -> Self {
+        Self { scenario, learner : None, step : 0, done : false }
+    }
+
+    pub fn get_scenario(&self) -> &'static Scenario {
+        self.scenario
+    }
+
+    /// from the first step again , for a new deal
+    pub fn restart(&mut self, learner : PlayerId) {
+        self.learner = Some(learner);
+        self.step = 0;
+        self.done = false;
+    }
+
+    fn prompt(&self) -> Option<Event> {
+        let step = self.scenario.steps.get(self.step)?;
+        Some(Event::TutorialStep { step : self.step, steps : self.scenario.steps.len(), prompt : step.prompt.into() })
+    }
+
+    /// the introduction , and the first step when the learner starts
+    pub fn opening(&self, turn : PlayerId) -> Vec<Event> {
+        let mut events = vec![Event::TutorialStarted { title : self.scenario.title.into(), intro : self.scenario.intro.into() }];
+        events.extend(self.prompt().filter(|_| self.learner == Some(turn)));
+        events
+    }
+
+    /// why `card` is not the move asked for , None when it is or the learner is not on turn
+    pub fn check(&self, p_id : PlayerId, turn : PlayerId, card : &Card) -> Option<String> {
+        if self.learner != Some(p_id) || turn != p_id {
+            return None;
+        }
+        let step = self.scenario.steps.get(self.step)?;
+        if self::card(step.expect).as_ref() == Ok(card) {
+            return None;
+        }
+        let mistake = step.mistakes.iter().find(|(c, _)| self::card(c).as_ref() == Ok(card)).map_or(step.otherwise, |(_, why)| why);
+        Some(mistake.into())
+    }
+
+    /// moves on after the learner's card , then asks for the next one when the turn comes back
+    pub fn observe(&mut self, events : &[Event]) -> Vec<Event> {
+        let Some(learner) = self.learner else {
+            return vec![];
+        };
+        if self.done {
+            return vec![];
+        }
+        if events.iter().any(|ev| matches!(ev, Event::CardPlayed { p_id, .. } if *p_id == learner)) {
+            self.step += 1;
+        }
+
+        let my_turn = events.iter().any(|ev| matches!(ev, Event::NextTurn { player_id } if *player_id == learner));
+        let over = events.iter().any(|ev| matches!(ev, Event::PlayerWon { player_id } if *player_id == learner) || matches!(ev, Event::EndGame { .. }));
+        if self.step < self.scenario.steps.len() {
+            return self.prompt().filter(|_| my_turn).into_iter().collect();
+        }
+        if my_turn || over {
+            self.done = true;
+            return vec![Event::TutorialDone { message : self.scenario.done.into() }];
+        }
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::profile::BotProfile;
+    use crate::game::actions::Action;
+    use crate::game::logic::{Game, GamePhase};
+    use crate::game::settings::RoomSettings;
+
+    #[test]
+    fn scenarios_are_valid() {
+        for scenario in SCENARIOS {
+            assert_eq!(scenario.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn bad_scenarios_are_refused() {
+        let twice = Scenario { id : "twice", title : "", intro : "", hands : &["AS 2H", "AS"], leader : 0, steps : &[], done : "" };
+        assert!(twice.validate().is_err());
+        let typo = Scenario { hands : &["AS 2X", "3S"], ..twice };
+        assert!(typo.validate().is_err());
+        let missing = Scenario {
+            hands : &["AS 2H", "3S"],
+            steps : &[Step { expect : "KD", prompt : "", mistakes : &[], otherwise : "" }],
+            ..typo
+        };
+        assert!(missing.validate().is_err());
+    }
+
+    /// the learner plays every `expect` card and the bots answer the way the ticker has them
+    #[test]
+    fn every_scenario_plays_to_the_end() {
+        for scenario in SCENARIOS {
+            let mut game = Game::with_settings(RoomSettings { max_players : scenario.players(), ..RoomSettings::default() });
+            game.add_player();
+            for _ in 1..scenario.players() {
+                game.add_bot(BotProfile::default());
+            }
+            game.set_tutorial(scenario);
+            game.start_game();
+
+            let learner = PlayerId(0);
+            let (mut step, mut done) = (0, false);
+            while game.get_phase() == GamePhase::Playing && !done {
+                let turn = game.get_turn();
+                let card = if turn == learner {
+                    let expect = scenario.steps.get(step).unwrap_or_else(|| panic!("{} : no step left for the learner", scenario.id)).expect;
+                    step += 1;
+                    expect.parse().unwrap()
+                } else {
+                    let mut bot = game.get_bot(turn).unwrap_or_default().make(game.get_settings().bot_search);
+                    bot.choose_card(&game, turn).unwrap_or_else(|| panic!("{} : bot has no card", scenario.id))
+                };
+                for ev in game.apply_action(Action::CardPlayedByPlayer { player_id : turn, card, suit : None }) {
+                    if let Event::TutorialMistake { message, .. } | Event::Error { message } = &ev {
+                        panic!("{} : {message}", scenario.id);
+                    }
+                    done |= matches!(ev, Event::TutorialDone { .. });
+                }
+            }
+            assert!(done, "{} never finished", scenario.id);
+            assert_eq!(step, scenario.steps.len(), "{}", scenario.id);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+use crate::game::card::Card;
+use crate::game::players::PlayerId;
+use crate::game::settings::GameMode;
+
+/// the table as one player sees it , their own hand and what is public
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerView {
+    pub me : PlayerId,
+    pub mode : GameMode,
+    pub hand : Vec<Card>,
+    /// what `me` may play right now , empty when it is not their turn
+    pub legal : Vec<Card>,
+    /// the current trick in play order
+    pub trick : Vec<(Card, PlayerId)>,
+    pub seats : Vec<PlayerId>,
+    /// cards held by each seat , in seat order
+    pub hand_sizes : Vec<usize>,
+    /// players already out , in no particular order
+    pub finished : Vec<PlayerId>,
+    pub turn : PlayerId,
+}
@@ -0,0 +1,5 @@
+// the game engine and bots , shared by the server and the headless tools in src/bin
+pub mod network;
+pub mod game;
+pub mod bot;
+pub mod env;
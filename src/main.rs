@@ -1,14 +1,34 @@
-use crate::{game::logic:: Game, network::game_route::build_router};
+use card_game::{game::logic:: Game, network::game_route::build_router};
+use card_game::network::matchmaking::MatchmakingConfig;
+use card_game::game::rules::HouseRules;
+use card_game::game::settings::RoomSettings;
+use card_game::bot::engine::Engines;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use crate::network::server::Server;
+use card_game::network::server::Server;
 // mark ready should contain name , and i should send seat as well as name along it , far better
-mod network;
-mod game;
 #[tokio::main]
 async fn main() {
-    let game = Arc::new(RwLock::new(Game::new()));
-    let app = build_router(game);
+    // every room on this server starts with these rules , the host can still change them
+    let rules = match std::env::var("HOUSE_RULES") {
+        Ok(path) => HouseRules::load(path.as_ref()).unwrap_or_else(|e| panic!("bad house rules {e}")),
+        Err(_) => HouseRules::default(),
+    };
+
+    // the only programs hosts may seat as bots
+    let engines = match std::env::var("BOT_ENGINES") {
+        Ok(path) => Engines::load(path.as_ref()).unwrap_or_else(|e| panic!("bad engine list {e}")),
+        Err(_) => Engines::default(),
+    };
+
+    let game = Arc::new(RwLock::new(Game::with_settings(RoomSettings { rules : rules.clone(), ..RoomSettings::default() })));
+    let mut matchmaking = MatchmakingConfig::default();
+    if let Some(secs) = std::env::var("BOT_BACKFILL_SECS").ok().and_then(|s| s.parse().ok()) {
+        matchmaking.bot_backfill_after = Duration::from_secs(secs);
+    }
+
+    let app = build_router(game, matchmaking, rules, engines);
     let addr = "0.0.0.0:3000";
 
     println!("🚀 Server running at http://0.0.0.0:3000");
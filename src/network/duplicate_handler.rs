@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde::{Serialize, Deserialize};
+use tokio::sync::broadcast::error::RecvError;
+use crate::game::card::Card;
+use crate::game::duplicate::{DuplicateScore, DuplicateSet, DuplicateTable};
+use crate::game::event::Event;
+use crate::game::settings::RoomSettings;
+use crate::network::rooms::{Room, RoomId};
+use crate::network::web_socket_handler::AppState;
+
+pub type DuplicateId = u32;
+
+struct Entry {
+    set : DuplicateSet,
+    /// room of every table , same order as `set.tables`
+    rooms : Vec<(RoomId, String)>,
+}
+
+#[derive(Default)]
+pub struct DuplicateSets {
+    entries : HashMap<DuplicateId, Entry>,
+    next_id : DuplicateId,
+}
+
+type ApiError = (StatusCode, String);
+
+#[derive(Deserialize)]
+pub struct CreateDuplicate {
+    pub table_size : usize,
+}
+
+/// a table of the set , join with `/ws?code=<code>`
+#[derive(Serialize)]
+pub struct TableView {
+    pub room_id : RoomId,
+    pub code : String,
+    #[serde(flatten)]
+    pub table : DuplicateTable,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateView {
+    pub id : DuplicateId,
+    pub table_size : usize,
+    pub tables : Vec<TableView>,
+    pub scores : Vec<DuplicateScore>,
+    /// only shown once every table is done , it gives away all hands
+    pub deck : Option<Vec<Card>>,
+}
+
+fn view(id : DuplicateId, entry : &Entry) -> DuplicateView {
+    let finished = entry.set.tables.iter().all(|t| t.result.is_some());
+    DuplicateView {
+        id,
+        table_size : entry.set.table_size,
+        tables : entry.set.tables.iter().zip(&entry.rooms).map(|(table, (room_id, code))| TableView {
+            room_id : *room_id,
+            code : code.clone(),
+            table : table.clone(),
+        }).collect(),
+        scores : entry.set.scores(),
+        deck : finished.then(|| entry.set.deck.clone()),
+    }
+}
+
+/// POST /duplicate , one private room per rotation of a freshly shuffled deal
+pub async fn create_duplicate(State(state) : State<AppState>, Json(req) : Json<CreateDuplicate>) -> Result<Json<DuplicateView>, ApiError> {
+    let set = DuplicateSet::new(req.table_size).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut duplicates = state.duplicates.lock().await;
+    let id = duplicates.next_id;
+    duplicates.next_id += 1;
+
+    let mut rooms = Vec::new();
+    for (idx, table) in set.tables.iter().enumerate() {
+        let room = {
+            let mut rooms = state.rooms.write().await;
+            let settings = RoomSettings { max_players : set.table_size, private : true, ..rooms.default_settings() };
+            rooms.create_managed(settings)
+        };
+        room.game.write().await.set_fixed_deal(set.deck.clone(), table.rotation);
+
+        rooms.push((room.id, room.code.clone()));
+        tokio::spawn(watch_table(state.clone(), id, idx, room));
+    }
+
+    let entry = Entry { set, rooms };
+    let res = view(id, &entry);
+    duplicates.entries.insert(id, entry);
+    Ok(Json(res))
+}
+
+/// GET /duplicate/:id , tables , results so far and scores
+pub async fn get_duplicate(State(state) : State<AppState>, Path(id) : Path<DuplicateId>) -> Result<Json<DuplicateView>, ApiError> {
+    let duplicates = state.duplicates.lock().await;
+    let entry = duplicates.entries.get(&id).ok_or((StatusCode::NOT_FOUND, "no such duplicate set".into()))?;
+    Ok(Json(view(id, entry)))
+}
+
+/// reports the first finished game at a table to its set
+/// only the receiver is kept , holding the room's sender would keep the channel open after the room is gone
+async fn watch_table(state : AppState, id : DuplicateId, table : usize, room : Room) {
+    let mut rx = room.tx.subscribe();
+    drop(room);
+
+    let summary = loop {
+        match rx.recv().await {
+            Ok(Event::GameOver { summary }) => break summary,
+            Ok(_) | Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        }
+    };
+
+    // names come with the summary , whoever left since then is still in it
+    let seats = summary.players.iter().map(|p| p.name.clone()).collect();
+    let places = summary.places();
+
+    if let Some(entry) = state.duplicates.lock().await.entries.get_mut(&id) {
+        entry.set.record_result(table, seats, places);
+    }
+}
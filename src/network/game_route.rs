@@ -1,24 +1,46 @@
 use std::sync::Arc;
-use tokio::sync::{RwLock, broadcast};
-use axum::{Router, response::Html, routing::get};
+use tokio::sync::{Mutex, RwLock};
+use axum::{Router, response::Html, routing::{get, post}};
 use tower_http::services::ServeDir;
 use crate::network::web_socket_handler::{AppState, ws_handler};
+use crate::network::room_handler::{create_room, list_engines, list_rooms, lobby_handler};
+use crate::network::rooms::Rooms;
+use crate::network::matchmaking::{MatchmakingConfig, Matchmaker, matchmaking_handler, run_matchmaking};
+use crate::network::duplicate_handler::{DuplicateSets, create_duplicate, get_duplicate};
+use crate::network::practice_handler::{create_practice, list_scenarios};
+use crate::network::tournament_handler::{Tournaments, create_tournament, get_tournament, register, start_tournament};
 use crate::game::logic::Game;
+use crate::game::rules::HouseRules;
+use crate::bot::engine::Engines;
 
 // use super::ws::ws_handler;
 
 
-pub fn build_router(game : Arc<RwLock<Game>>) -> Router {
-    let (tx , _) = broadcast::channel(1024);
-    
+pub fn build_router(game : Arc<RwLock<Game>>, matchmaking : MatchmakingConfig, rules : HouseRules, engines : Engines) -> Router {
+    // the game created in main becomes the default public table
     let state = AppState{
-        game,
-        tx,
+        rooms : Arc::new(RwLock::new(Rooms::new(game, rules, engines))),
+        matchmaker : Arc::new(Mutex::new(Matchmaker::new(matchmaking))),
+        tournaments : Arc::new(Mutex::new(Tournaments::default())),
+        duplicates : Arc::new(Mutex::new(DuplicateSets::default())),
     };
+    tokio::spawn(run_matchmaking(state.clone()));
+
     Router::new()
 
     .route("/", get(index))
     .route("/ws" , get(ws_handler))
+    .route("/rooms" , get(list_rooms).post(create_room))
+    .route("/lobby" , get(lobby_handler))
+    .route("/engines" , get(list_engines))
+    .route("/matchmaking" , get(matchmaking_handler))
+    .route("/tournaments" , post(create_tournament))
+    .route("/tournaments/:id" , get(get_tournament))
+    .route("/tournaments/:id/register" , post(register))
+    .route("/tournaments/:id/start" , post(start_tournament))
+    .route("/duplicate" , post(create_duplicate))
+    .route("/duplicate/:id" , get(get_duplicate))
+    .route("/practice" , get(list_scenarios).post(create_practice))
     .nest_service(
             "/assets",
             ServeDir::new("src/game_assets/assets"),
@@ -0,0 +1,275 @@
+use std::time::{Duration, Instant};
+use axum::{
+    extract::{Query, State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    response::IntoResponse,
+};
+use serde::{Serialize, Deserialize};
+use tokio::sync::oneshot;
+use crate::game::settings::{RoomSettings, MAX_PLAYERS, MIN_PLAYERS};
+use crate::bot::profile::BotProfile;
+use crate::network::rooms::{RoomId, expire_unjoined};
+use crate::network::web_socket_handler::AppState;
+
+/// how far apart ratings may be right away , grows the longer people wait
+const BASE_RATING_SPREAD : i32 = 100;
+const RATING_SPREAD_PER_SEC : i32 = 20;
+
+/// matched rooms nobody shows up to are closed after this
+const JOIN_GRACE : Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct MatchmakingConfig {
+    /// fill the table with bots once the oldest player waited this long
+    pub bot_backfill_after : Duration,
+}
+
+impl Default for MatchmakingConfig {
+    fn default() -> Self {
+        Self { bot_backfill_after : Duration::from_secs(30) }
+    }
+}
+
+/// sent on the `/matchmaking` socket
+#[derive(Debug, Clone, Serialize)]
+pub enum MatchMsg {
+    Queued {table_size : usize},
+    /// connect to `/ws?code=<code>` to take the seat
+    MatchFound {room_id : RoomId , code : String , bots : usize},
+    Error {message : String},
+}
+
+#[derive(Deserialize)]
+pub struct QueueParams {
+    size : usize,
+    rating : Option<i32>,
+}
+
+struct Ticket {
+    size : usize,
+    rating : Option<i32>,
+    joined : Instant,
+    notify : oneshot::Sender<MatchMsg>,
+}
+
+pub struct Matchmaker {
+    config : MatchmakingConfig,
+    queue : Vec<Ticket>,
+}
+
+impl Matchmaker {
+    pub fn new(config : MatchmakingConfig) -> Self {
+        Self { config, queue : Vec::new() }
+    }
+
+    /// takes the next group to seat out of the queue , together with how many bots it needs
+    fn next_group(&mut self, now : Instant) -> Option<(Vec<Ticket>, usize)> {
+        // players who gave up waiting
+        self.queue.retain(|t| !t.notify.is_closed());
+
+        for size in MIN_PLAYERS..=MAX_PLAYERS {
+            let mut waiting : Vec<usize> = (0..self.queue.len()).filter(|&i| self.queue[i].size == size).collect();
+            if waiting.is_empty() {
+                continue;
+            }
+            waiting.sort_by_key(|&i| self.queue[i].rating.unwrap_or(i32::MIN));
+
+            let oldest = waiting.iter().map(|&i| self.queue[i].joined).min().expect("not empty");
+            let spread = BASE_RATING_SPREAD + RATING_SPREAD_PER_SEC * now.duration_since(oldest).as_secs() as i32;
+
+            let group = waiting
+                .windows(size)
+                .find(|w| fits(w.iter().map(|&i| self.queue[i].rating), spread))
+                .map(|w| w.to_vec());
+
+            if let Some(group) = group {
+                return Some((self.take(group), 0));
+            }
+
+            if now.duration_since(oldest) >= self.config.bot_backfill_after {
+                // longest waiting first
+                waiting.sort_by_key(|&i| self.queue[i].joined);
+                waiting.truncate(size);
+                let bots = size - waiting.len();
+                return Some((self.take(waiting), bots));
+            }
+        }
+        None
+    }
+
+    fn take(&mut self, mut idx : Vec<usize>) -> Vec<Ticket> {
+        idx.sort_unstable_by(|a, b| b.cmp(a));
+        idx.into_iter().map(|i| self.queue.swap_remove(i)).collect()
+    }
+}
+
+/// unrated players fit with anyone
+fn fits(ratings : impl Iterator<Item = Option<i32>>, spread : i32) -> bool {
+    let rated : Vec<i32> = ratings.flatten().collect();
+    match (rated.iter().min(), rated.iter().max()) {
+        (Some(lo), Some(hi)) => hi - lo <= spread,
+        _ => true,
+    }
+}
+
+/// GET /matchmaking?size=4&rating=1500 , stays open until a table is found
+pub async fn matchmaking_handler(ws : WebSocketUpgrade, State(state) : State<AppState>, Query(params) : Query<QueueParams>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| wait_for_match(socket, state, params))
+}
+
+async fn wait_for_match(mut socket : WebSocket, state : AppState, params : QueueParams) {
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&params.size) {
+        let msg = MatchMsg::Error { message : format!("table size must be between {MIN_PLAYERS} and {MAX_PLAYERS}") };
+        let _ = send_msg(&mut socket, &msg).await;
+        return;
+    }
+
+    let (notify, found) = oneshot::channel();
+    state.matchmaker.lock().await.queue.push(Ticket {
+        size : params.size,
+        rating : params.rating,
+        joined : Instant::now(),
+        notify,
+    });
+
+    if send_msg(&mut socket, &MatchMsg::Queued { table_size : params.size }).await.is_err() {
+        return;
+    }
+
+    // closing the socket drops `found` which takes the ticket out of the queue
+    tokio::select! {
+        msg = found => {
+            if let Ok(msg) = msg {
+                let _ = send_msg(&mut socket, &msg).await;
+            }
+        }
+        _ = wait_for_close(&mut socket) => {}
+    }
+}
+
+async fn wait_for_close(socket : &mut WebSocket) {
+    while let Some(Ok(msg)) = socket.recv().await {
+        if matches!(msg, Message::Close(_)) {
+            return;
+        }
+    }
+}
+
+async fn send_msg(socket : &mut WebSocket, msg : &MatchMsg) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(msg).expect("match message serializes");
+    socket.send(Message::Text(json)).await
+}
+
+/// groups waiting players into new private rooms , once a second
+pub async fn run_matchmaking(state : AppState) {
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        tick.tick().await;
+
+        loop {
+            let Some((tickets, bots)) = state.matchmaker.lock().await.next_group(Instant::now()) else {
+                break;
+            };
+            seat_group(&state, tickets, bots).await;
+        }
+    }
+}
+
+async fn seat_group(state : &AppState, tickets : Vec<Ticket>, bots : usize) {
+    let size = tickets.len() + bots;
+    let room = {
+        let mut rooms = state.rooms.write().await;
+        let settings = RoomSettings { max_players : size, private : true, ..rooms.default_settings() };
+        rooms.create(settings, None)
+    };
+
+    {
+        let mut game = room.game.write().await;
+        for _ in 0..bots {
+            if let Some(ev) = game.add_bot(BotProfile::default()) {
+                let _ = room.tx.send(ev);
+            }
+        }
+    }
+
+    for ticket in tickets {
+        let _ = ticket.notify.send(MatchMsg::MatchFound { room_id : room.id, code : room.code.clone(), bots });
+    }
+
+    // close the room again if nobody takes their seat
+    expire_unjoined(state.rooms.clone(), &room, JOIN_GRACE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// queues a ticket , the receiver has to be kept or the ticket counts as given up
+    fn queue(mm : &mut Matchmaker, size : usize, rating : Option<i32>, joined : Instant) -> oneshot::Receiver<MatchMsg> {
+        let (notify, found) = oneshot::channel();
+        mm.queue.push(Ticket { size, rating, joined, notify });
+        found
+    }
+
+    fn ratings(group : &[Ticket]) -> Vec<Option<i32>> {
+        let mut ratings : Vec<_> = group.iter().map(|t| t.rating).collect();
+        ratings.sort();
+        ratings
+    }
+
+    #[test]
+    fn unrated_players_fit_with_anyone() {
+        assert!(fits([Some(1000), None, Some(1100)].into_iter(), 100));
+        assert!(!fits([Some(1000), None, Some(1101)].into_iter(), 100));
+        assert!(fits([None, None].into_iter(), 0));
+    }
+
+    #[test]
+    fn close_ratings_are_seated_together() {
+        let mut mm = Matchmaker::new(MatchmakingConfig::default());
+        let t0 = Instant::now();
+        let _waiting : Vec<_> = [1000, 1500, 1060, 1090].map(|r| queue(&mut mm, 3, Some(r), t0)).into();
+
+        let (group, bots) = mm.next_group(t0).expect("three are within the spread");
+        assert_eq!(ratings(&group), vec![Some(1000), Some(1060), Some(1090)]);
+        assert_eq!(bots, 0);
+        assert_eq!(mm.queue.len(), 1);
+    }
+
+    #[test]
+    fn the_spread_grows_while_waiting() {
+        let mut mm = Matchmaker::new(MatchmakingConfig::default());
+        let t0 = Instant::now();
+        let _waiting : Vec<_> = [1000, 1250].map(|r| queue(&mut mm, 2, Some(r), t0)).into();
+
+        assert!(mm.next_group(t0).is_none());
+        assert!(mm.next_group(t0 + Duration::from_secs(7)).is_none());
+        let (group, _) = mm.next_group(t0 + Duration::from_secs(8)).expect("the spread is 260 by now");
+        assert_eq!(group.len(), 2);
+    }
+
+    #[test]
+    fn bots_fill_the_table_after_the_wait() {
+        let config = MatchmakingConfig { bot_backfill_after : Duration::from_secs(30) };
+        let mut mm = Matchmaker::new(config);
+        let t0 = Instant::now();
+        let _first = queue(&mut mm, 4, Some(1000), t0);
+        let _second = queue(&mut mm, 4, None, t0 + Duration::from_secs(5));
+
+        assert!(mm.next_group(t0 + Duration::from_secs(29)).is_none());
+        let (group, bots) = mm.next_group(t0 + Duration::from_secs(30)).expect("the oldest waited long enough");
+        assert_eq!((group.len(), bots), (2, 2));
+        assert!(mm.queue.is_empty());
+    }
+
+    #[test]
+    fn tickets_given_up_leave_the_queue() {
+        let mut mm = Matchmaker::new(MatchmakingConfig::default());
+        let t0 = Instant::now();
+        let _kept = queue(&mut mm, 2, None, t0);
+        drop(queue(&mut mm, 2, None, t0));
+
+        assert!(mm.next_group(t0).is_none());
+        assert_eq!(mm.queue.len(), 1);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod server;
 pub mod game_route;
 pub mod web_socket_handler;
-pub mod Messages;
\ No newline at end of file
+pub mod messages;
+pub mod ticker;
+pub mod rooms;
+pub mod room_handler;
+pub mod matchmaking;
+pub mod tournament_handler;
+pub mod duplicate_handler;
+pub mod practice_handler;
\ No newline at end of file
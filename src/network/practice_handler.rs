@@ -0,0 +1,55 @@
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+};
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use crate::bot::profile::BotProfile;
+use crate::game::settings::RoomSettings;
+use crate::game::tutorial::{self, ScenarioInfo, SCENARIOS};
+use crate::network::rooms::{RoomId, expire_unjoined};
+use crate::network::web_socket_handler::AppState;
+
+type ApiError = (StatusCode, String);
+
+/// practice rooms nobody joined are closed after this
+const JOIN_GRACE : Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+pub struct CreatePractice {
+    pub scenario : String,
+}
+
+/// join with `/ws?code=<code>` and send Ready , the bots are already seated
+#[derive(Serialize)]
+pub struct PracticeRoom {
+    pub room_id : RoomId,
+    pub code : String,
+    pub scenario : ScenarioInfo,
+}
+
+/// GET /practice , the scenarios to pick from
+pub async fn list_scenarios() -> Json<Vec<ScenarioInfo>> {
+    Json(SCENARIOS.iter().map(ScenarioInfo::from).collect())
+}
+
+/// POST /practice , a private room dealing the scenario with bots in every other seat
+pub async fn create_practice(State(state) : State<AppState>, Json(req) : Json<CreatePractice>) -> Result<Json<PracticeRoom>, ApiError> {
+    let scenario = tutorial::find(&req.scenario).ok_or((StatusCode::NOT_FOUND, format!("no scenario called {}", req.scenario)))?;
+    scenario.validate().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // scenarios are written for the default house rules
+    let settings = RoomSettings { max_players : scenario.players(), private : true, ..RoomSettings::default() };
+    let room = state.rooms.write().await.create(settings, None);
+    {
+        let mut game = room.game.write().await;
+        for _ in 1..scenario.players() {
+            game.add_bot(BotProfile::default());
+        }
+        game.set_tutorial(scenario);
+    }
+    expire_unjoined(state.rooms.clone(), &room, JOIN_GRACE);
+
+    Ok(Json(PracticeRoom { room_id : room.id, code : room.code.clone(), scenario : scenario.into() }))
+}
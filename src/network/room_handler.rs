@@ -0,0 +1,132 @@
+use axum::{
+    Json,
+    extract::{State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    response::IntoResponse,
+};
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use tokio::sync::{RwLock, broadcast};
+use crate::game::settings::RoomSettings;
+use crate::game::rules::HouseRules;
+use crate::network::rooms::{LobbyUpdate, RoomId, RoomInfo, Rooms, expire_unjoined};
+use crate::network::web_socket_handler::AppState;
+
+/// a room nobody joined is closed after this , the code has to be passed round first
+const JOIN_GRACE : Duration = Duration::from_secs(10 * 60);
+
+#[derive(Deserialize)]
+pub struct CreateRoom {
+    #[serde(default)]
+    pub private : bool,
+    pub password : Option<String>,
+    /// house rules for this room , the server's rules when left out
+    pub rules : Option<HouseRules>,
+}
+
+#[derive(Serialize)]
+pub struct RoomCreated {
+    pub room_id : RoomId,
+    /// share this with friends , private rooms can only be joined with it
+    pub code : String,
+}
+
+/// POST /rooms
+pub async fn create_room(State(state) : State<AppState>, Json(req) : Json<CreateRoom>) -> Json<RoomCreated> {
+    let password = req.password.filter(|p| !p.is_empty());
+    let mut rooms = state.rooms.write().await;
+    let mut settings = RoomSettings { private : req.private, ..rooms.default_settings() };
+    if let Some(rules) = req.rules {
+        settings.rules = rules;
+    }
+
+    let room = rooms.create(settings, password);
+    expire_unjoined(state.rooms.clone(), &room, JOIN_GRACE);
+    Json(RoomCreated { room_id : room.id, code : room.code })
+}
+
+/// GET /rooms , every public room sorted by id
+pub async fn list_rooms(State(state) : State<AppState>) -> Json<Vec<RoomInfo>> {
+    Json(public_rooms(&state.rooms).await)
+}
+
+/// GET /engines , the outside programs hosts can seat as bots
+pub async fn list_engines(State(state) : State<AppState>) -> Json<Vec<String>> {
+    Json(state.rooms.read().await.engine_names())
+}
+
+/// GET /lobby , full room list once and then every change as it happens
+pub async fn lobby_handler(ws : WebSocketUpgrade, State(state) : State<AppState>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_lobby(socket, state))
+}
+
+async fn handle_lobby(mut socket : WebSocket, state : AppState) {
+    // subscribe first so nothing happening while the list is built gets lost
+    let mut rx = state.rooms.read().await.subscribe_lobby();
+
+    let rooms = public_rooms(&state.rooms).await;
+    if send_update(&mut socket, &LobbyUpdate::Rooms { rooms }).await.is_err() {
+        return;
+    }
+
+    loop {
+        let update = match rx.recv().await {
+            Ok(update) => update,
+            // fell behind , start over with a fresh list
+            Err(broadcast::error::RecvError::Lagged(_)) => LobbyUpdate::Rooms { rooms : public_rooms(&state.rooms).await },
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        if send_update(&mut socket, &update).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn send_update(socket : &mut WebSocket, update : &LobbyUpdate) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(update).expect("lobby update serializes");
+    socket.send(Message::Text(json)).await
+}
+
+async fn public_rooms(rooms : &RwLock<Rooms>) -> Vec<RoomInfo> {
+    let rooms = rooms.read().await.all();
+
+    let mut infos = Vec::new();
+    for room in rooms {
+        let info = room.info().await;
+        if !info.settings.private {
+            infos.push(info);
+        }
+    }
+    infos.sort_by_key(|r| r.room_id);
+    infos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::bot::engine::Engines;
+    use crate::game::actions::Action;
+    use crate::game::logic::Game;
+    use crate::game::players::PlayerId;
+    use crate::network::rooms::DEFAULT_ROOM;
+
+    #[tokio::test]
+    async fn listing_leaves_out_private_rooms() {
+        let rooms = RwLock::new(Rooms::new(Arc::new(RwLock::new(Game::new())), HouseRules::default(), Engines::default()));
+        let (public, private, turned) = {
+            let mut rooms = rooms.write().await;
+            let public = rooms.create(RoomSettings::default(), Some("pw".into()));
+            let private = rooms.create(RoomSettings { private : true, ..RoomSettings::default() }, None);
+            (public, private, rooms.create(RoomSettings::default(), None))
+        };
+        {
+            let mut game = turned.game.write().await;
+            game.add_player();
+            game.apply_action(Action::UpdateSettings { player_id : PlayerId(0), settings : RoomSettings { private : true, ..RoomSettings::default() } });
+        }
+
+        let listed : Vec<RoomId> = public_rooms(&rooms).await.iter().map(|r| r.room_id).collect();
+        assert_eq!(listed, vec![DEFAULT_ROOM, public.id]);
+        assert!(!listed.contains(&private.id));
+    }
+}
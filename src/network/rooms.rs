@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use rand::Rng;
+use serde::Serialize;
+use tokio::sync::{RwLock, broadcast};
+use tokio::task::AbortHandle;
+use crate::game::event::Event;
+use crate::game::logic::{Game, GamePhase};
+use crate::game::settings::RoomSettings;
+use crate::game::rules::HouseRules;
+use crate::network::ticker::run_room_ticker;
+use crate::bot::engine::Engines;
+
+pub type RoomId = u32;
+
+/// the table everyone lands on when `/ws` is opened without a room
+pub const DEFAULT_ROOM : RoomId = 0;
+
+const CODE_LEN : usize = 6;
+// no 0/O or 1/I so codes can be read out loud
+const CODE_CHARS : &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// what the lobby browser shows for a table
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomInfo {
+    pub room_id : RoomId,
+    pub phase : GamePhase,
+    pub seats_taken : usize,
+    pub players : Vec<String>,
+    pub settings : RoomSettings,
+}
+
+/// pushed to everyone on the lobby channel
+#[derive(Debug, Clone, Serialize)]
+pub enum LobbyUpdate {
+    /// full list , sent once when connecting
+    Rooms {rooms : Vec<RoomInfo>},
+    RoomUpdated {room : RoomInfo},
+    /// room was closed or turned private
+    RoomClosed {room_id : RoomId},
+}
+
+/// one table with its own game and broadcast channel
+#[derive(Clone)]
+pub struct Room {
+    pub id : RoomId,
+    pub code : String,
+    password : Option<String>,
+    /// only these names may sit here , None means anyone
+    pub reserved : Option<Vec<String>>,
+    /// opened by a tournament or duplicate set , stays open while empty so people can come back
+    pub managed : bool,
+    pub game : Arc<RwLock<Game>>,
+    pub tx : broadcast::Sender<Event>,
+    /// outside programs bots at this table may be played by
+    pub engines : Arc<Engines>,
+    tasks : Vec<AbortHandle>,
+}
+
+impl Room {
+    fn new(id : RoomId, code : String, game : Arc<RwLock<Game>>, password : Option<String>, lobby_tx : broadcast::Sender<LobbyUpdate>, engines : Arc<Engines>) -> Self {
+        let (tx , _) = broadcast::channel(1024);
+        let ticker = tokio::spawn(run_room_ticker(game.clone(), tx.clone(), engines.clone())).abort_handle();
+        let watcher = tokio::spawn(watch_room(id, game.clone(), tx.subscribe(), lobby_tx)).abort_handle();
+
+        Self { id, code, password, reserved : None, managed : false, game, tx, engines, tasks : vec![ticker, watcher] }
+    }
+
+    pub async fn info(&self) -> RoomInfo {
+        room_info(self.id, &*self.game.read().await)
+    }
+
+    /// rooms without a password accept anything
+    pub fn check_password(&self, given : Option<&str>) -> bool {
+        match &self.password {
+            Some(password) => given == Some(password.as_str()),
+            None => true,
+        }
+    }
+}
+
+fn room_info(room_id : RoomId, game : &Game) -> RoomInfo {
+    RoomInfo {
+        room_id,
+        phase : game.get_phase(),
+        seats_taken : game.get_seats().len(),
+        players : game.get_names(),
+        settings : game.get_settings().clone(),
+    }
+}
+
+fn changes_listing(ev : &Event) -> bool {
+    matches!(ev,
+        Event::PlayerAdded { .. }
+        | Event::PlayerLeft { .. }
+        | Event::StartGame
+        | Event::AbortGame
+        | Event::EndGame { .. }
+        | Event::ReturnToLobby
+        | Event::SettingsChanged { .. }
+    )
+}
+
+/// keeps the lobby channel up to date with what happens at one table
+async fn watch_room(id : RoomId, game : Arc<RwLock<Game>>, mut rx : broadcast::Receiver<Event>, lobby_tx : broadcast::Sender<LobbyUpdate>) {
+    loop {
+        match rx.recv().await {
+            Ok(ev) if !changes_listing(&ev) => continue,
+            // missed events are fine , the listing is rebuilt from the game anyway
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+
+        let info = room_info(id, &*game.read().await);
+        let update = if info.settings.private {
+            LobbyUpdate::RoomClosed { room_id : id }
+        } else {
+            LobbyUpdate::RoomUpdated { room : info }
+        };
+        let _ = lobby_tx.send(update);
+    }
+}
+
+pub struct Rooms {
+    rooms : HashMap<RoomId, Room>,
+    next_id : RoomId,
+    lobby_tx : broadcast::Sender<LobbyUpdate>,
+    /// house rules new rooms start with
+    rules : HouseRules,
+    engines : Arc<Engines>,
+}
+
+impl Rooms {
+    pub fn new(default_game : Arc<RwLock<Game>>, rules : HouseRules, engines : Engines) -> Self {
+        let (lobby_tx , _) = broadcast::channel(256);
+        let mut rooms = Self { rooms : HashMap::new(), next_id : DEFAULT_ROOM + 1, lobby_tx, rules, engines : Arc::new(engines) };
+        let code = rooms.new_code();
+        let room = Room::new(DEFAULT_ROOM, code, default_game, None, rooms.lobby_tx.clone(), rooms.engines.clone());
+        rooms.rooms.insert(DEFAULT_ROOM, room);
+        rooms
+    }
+
+    pub fn subscribe_lobby(&self) -> broadcast::Receiver<LobbyUpdate> {
+        self.lobby_tx.subscribe()
+    }
+
+    /// settings for a fresh room , the server's house rules and everything else default
+    pub fn default_settings(&self) -> RoomSettings {
+        RoomSettings { rules : self.rules.clone(), ..RoomSettings::default() }
+    }
+
+    /// names of the engines hosts can seat
+    pub fn engine_names(&self) -> Vec<String> {
+        self.engines.names()
+    }
+
+    pub fn all(&self) -> Vec<Room> {
+        self.rooms.values().cloned().collect()
+    }
+
+    pub fn create(&mut self, settings : RoomSettings, password : Option<String>) -> Room {
+        self.insert(Game::with_settings(settings), password, None, false)
+    }
+
+    /// a room a duplicate set looks after , it isn't closed when the last player leaves and the host can't change it
+    pub fn create_managed(&mut self, settings : RoomSettings) -> Room {
+        self.insert(Game::with_settings(settings), None, None, true)
+    }
+
+    /// a private room only the given names can join , used for tournament tables
+    pub fn create_reserved(&mut self, settings : RoomSettings, names : Vec<String>) -> Room {
+        let mut game = Game::with_settings(settings);
+        game.lock_names();
+        self.insert(game, None, Some(names), true)
+    }
+
+    fn insert(&mut self, mut game : Game, password : Option<String>, reserved : Option<Vec<String>>, managed : bool) -> Room {
+        if managed {
+            game.set_managed();
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+
+        if !game.get_settings().private {
+            let _ = self.lobby_tx.send(LobbyUpdate::RoomUpdated { room : room_info(id, &game) });
+        }
+
+        let code = self.new_code();
+        let mut room = Room::new(id, code, Arc::new(RwLock::new(game)), password, self.lobby_tx.clone(), self.engines.clone());
+        room.managed = managed;
+        room.reserved = reserved;
+        self.rooms.insert(id, room.clone());
+        room
+    }
+
+    pub fn get(&self, id : RoomId) -> Option<Room> {
+        self.rooms.get(&id).cloned()
+    }
+
+    /// codes are matched case insensitive so people can type them however
+    pub fn find_by_code(&self, code : &str) -> Option<Room> {
+        let code = code.trim().to_ascii_uppercase();
+        self.rooms.values().find(|r| r.code == code).cloned()
+    }
+
+    /// drops a room and stops its tasks , the default room is never removed
+    pub fn remove(&mut self, id : RoomId) {
+        if id == DEFAULT_ROOM {
+            return;
+        }
+        if let Some(room) = self.rooms.remove(&id) {
+            for task in room.tasks {
+                task.abort();
+            }
+            let _ = self.lobby_tx.send(LobbyUpdate::RoomClosed { room_id : id });
+        }
+    }
+
+    fn new_code(&self) -> String {
+        let mut rng = rand::rng();
+        loop {
+            let code : String = (0..CODE_LEN)
+                .map(|_| CODE_CHARS[rng.random_range(0..CODE_CHARS.len())] as char)
+                .collect();
+            if !self.rooms.values().any(|r| r.code == code) {
+                return code;
+            }
+        }
+    }
+}
+
+/// closes `room` after `grace` if nobody took a seat by then , people sitting keep it open
+pub fn expire_unjoined(rooms : Arc<RwLock<Rooms>>, room : &Room, grace : Duration) {
+    let id = room.id;
+    let game = room.game.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(grace).await;
+        let mut rooms = rooms.write().await;
+        if game.read().await.is_empty() {
+            rooms.remove(id);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::profile::BotProfile;
+    use crate::game::actions::Action;
+    use crate::game::players::PlayerId;
+
+    fn rooms() -> Arc<RwLock<Rooms>> {
+        let default_game = Arc::new(RwLock::new(Game::new()));
+        Arc::new(RwLock::new(Rooms::new(default_game, HouseRules::default(), Engines::default())))
+    }
+
+    #[tokio::test]
+    async fn managed_rooms_are_stored_managed() {
+        let rooms = rooms();
+        let mut rooms = rooms.write().await;
+        let managed = rooms.create_managed(RoomSettings::default());
+        let plain = rooms.create(RoomSettings::default(), None);
+
+        assert!(managed.managed && rooms.get(managed.id).is_some_and(|r| r.managed));
+        assert!(!plain.managed && rooms.get(plain.id).is_some_and(|r| !r.managed));
+
+        let mut game = managed.game.write().await;
+        game.add_player();
+        let events = game.apply_action(Action::AddBot { player_id : PlayerId(0), profile : BotProfile::default() });
+        assert!(matches!(events.as_slice(), [Event::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn codes_and_passwords() {
+        let rooms = rooms();
+        let room = rooms.write().await.create(RoomSettings::default(), Some("hunter2".into()));
+
+        let found = rooms.read().await.find_by_code(&format!(" {} ", room.code.to_ascii_lowercase()));
+        assert_eq!(found.map(|r| r.id), Some(room.id));
+        assert!(room.check_password(Some("hunter2")));
+        assert!(!room.check_password(Some("hunter")));
+        assert!(!room.check_password(None));
+        assert!(rooms.read().await.get(DEFAULT_ROOM).is_some_and(|r| r.check_password(None)));
+    }
+
+    #[tokio::test]
+    async fn rooms_nobody_joined_expire() {
+        let rooms = rooms();
+        let (empty, joined) = {
+            let mut rooms = rooms.write().await;
+            (rooms.create(RoomSettings::default(), None), rooms.create(RoomSettings::default(), None))
+        };
+        joined.game.write().await.add_player();
+
+        let grace = Duration::from_millis(20);
+        expire_unjoined(rooms.clone(), &empty, grace);
+        expire_unjoined(rooms.clone(), &joined, grace);
+        assert!(rooms.read().await.get(empty.id).is_some());
+
+        tokio::time::sleep(grace * 5).await;
+        let rooms = rooms.read().await;
+        assert!(rooms.get(empty.id).is_none());
+        assert!(rooms.get(joined.id).is_some());
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, broadcast};
+use crate::bot::Bot;
+use crate::bot::engine::{EngineBot, Engines};
+use crate::game::actions::Action;
+use crate::game::event::Event;
+use crate::game::logic::Game;
+use crate::game::players::PlayerId;
+
+/// once a second : plays for a player who ran out of time , and lets a bot on turn make its move
+pub async fn run_room_ticker(game : Arc<RwLock<Game>>, tx : broadcast::Sender<Event>, engines : Arc<Engines>) {
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+    // engine processes live as long as their seat , they are killed when dropped
+    let mut running : HashMap<PlayerId, EngineBot> = HashMap::new();
+    let mut rx = tx.subscribe();
+
+    loop {
+        tick.tick().await;
+
+        let (p_id, started, snapshot) = {
+            let mut game = game.write().await;
+
+            running.retain(|&p_id, engine| game.get_bot(p_id).and_then(|b| b.engine).as_deref() == Some(engine.get_name()));
+            loop {
+                match rx.try_recv() {
+                    Ok(ev) => running.values_mut().for_each(|engine| engine.observe(&ev)),
+                    Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+
+            match game.bot_to_move() {
+                Some(p_id) => (p_id, game.get_turn_started(), game.clone()),
+                None if game.turn_timed_out(Instant::now()) => {
+                    for ev in game.auto_play() {
+                        let _ = tx.send(ev);
+                    }
+                    continue;
+                }
+                None => continue,
+            }
+        };
+
+        // searching and engines take a while , they work on a copy so the table isn't locked meanwhile
+        let action = tokio::task::block_in_place(|| bot_action(&snapshot, p_id, &mut running, &engines));
+        let Some(action) = action else {
+            continue;
+        };
+
+        let events = {
+            let mut game = game.write().await;
+            // someone left , timed out or restarted the game while the bot was thinking
+            if game.bot_to_move() != Some(p_id) || game.get_turn_started() != started {
+                continue;
+            }
+            game.apply_action(action)
+        };
+
+        for ev in events {
+            let _ = tx.send(ev);
+        }
+    }
+}
+
+/// what the bot in seat `p_id` does next , a pass , a bid , a card or a draw , whatever the game is waiting for
+fn bot_action(game : &Game, p_id : PlayerId, running : &mut HashMap<PlayerId, EngineBot>, engines : &Engines) -> Option<Action> {
+    let profile = game.get_bot(p_id).unwrap_or_default();
+    let fallback = profile.make(game.get_settings().bot_search);
+    let mut built;
+    let bot : &mut dyn Bot = match profile.engine.as_deref().and_then(|name| Some((name, engines.get(name)?))) {
+        Some((name, config)) => running.entry(p_id).or_insert_with(|| EngineBot::new(name, config, fallback)),
+        None => {
+            built = fallback;
+            built.as_mut()
+        }
+    };
+
+    if game.is_passing() {
+        let cards = bot.choose_pass(game, p_id);
+        return Some(Action::PassCards { player_id: p_id, cards });
+    }
+    if game.is_bidding() {
+        let bid = bot.choose_bid(game, p_id);
+        return Some(Action::Bid { player_id: p_id, bid });
+    }
+    match bot.choose_card(game, p_id) {
+        Some(card) => Some(Action::CardPlayedByPlayer { player_id: p_id, card, suit: None }),
+        None if game.must_draw(p_id) => Some(Action::DrawCard { player_id: p_id }),
+        None => None,
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use rand::{Rng, distr::Alphanumeric};
+use serde::{Serialize, Deserialize};
+use tokio::sync::broadcast::error::RecvError;
+use crate::game::event::Event;
+use crate::game::settings::RoomSettings;
+use crate::game::tournament::{Entrant, Stage, Table, Tournament};
+use crate::network::rooms::{Room, RoomId};
+use crate::network::web_socket_handler::AppState;
+
+pub type TournamentId = u32;
+
+const TOKEN_LEN : usize = 16;
+
+/// a table whose game hasn't started by then is scored without whoever didn't show up
+const NO_SHOW_GRACE : Duration = Duration::from_secs(5 * 60);
+
+struct Entry {
+    tournament : Tournament,
+    /// whoever created the tournament needs this to start it
+    admin_token : String,
+    /// room of every table in the current stage , same order as `tournament.tables`
+    rooms : Vec<(RoomId, String)>,
+}
+
+#[derive(Default)]
+pub struct Tournaments {
+    entries : HashMap<TournamentId, Entry>,
+    next_id : TournamentId,
+}
+
+type ApiError = (StatusCode, String);
+
+#[derive(Deserialize)]
+pub struct CreateTournament {
+    pub name : String,
+    pub table_size : usize,
+    pub rounds : u32,
+}
+
+#[derive(Serialize)]
+pub struct TournamentCreated {
+    pub id : TournamentId,
+    pub admin_token : String,
+}
+
+#[derive(Deserialize)]
+pub struct Register {
+    pub name : String,
+}
+
+#[derive(Deserialize)]
+pub struct StartTournament {
+    pub admin_token : String,
+}
+
+/// a table of the current stage , join with `/ws?code=<code>&name=<your name>`
+#[derive(Serialize)]
+pub struct TableView {
+    pub room_id : RoomId,
+    pub code : String,
+    #[serde(flatten)]
+    pub table : Table,
+}
+
+#[derive(Serialize)]
+pub struct Bracket {
+    pub id : TournamentId,
+    pub name : String,
+    pub stage : Stage,
+    pub table_size : usize,
+    pub rounds : u32,
+    pub standings : Vec<Entrant>,
+    pub tables : Vec<TableView>,
+    pub history : Vec<Vec<Table>>,
+    pub winners : Vec<String>,
+}
+
+fn bracket(id : TournamentId, entry : &Entry) -> Bracket {
+    let t = &entry.tournament;
+    Bracket {
+        id,
+        name : t.name.clone(),
+        stage : t.stage,
+        table_size : t.table_size,
+        rounds : t.rounds,
+        standings : t.standings(),
+        tables : t.tables.iter().zip(&entry.rooms).map(|(table, (room_id, code))| TableView {
+            room_id : *room_id,
+            code : code.clone(),
+            table : table.clone(),
+        }).collect(),
+        history : t.history.clone(),
+        winners : t.winners.clone(),
+    }
+}
+
+/// POST /tournaments
+pub async fn create_tournament(State(state) : State<AppState>, Json(req) : Json<CreateTournament>) -> Result<Json<TournamentCreated>, ApiError> {
+    let tournament = Tournament::new(req.name, req.table_size, req.rounds)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let admin_token : String = rand::rng().sample_iter(&Alphanumeric).take(TOKEN_LEN).map(char::from).collect();
+
+    let mut tournaments = state.tournaments.lock().await;
+    let id = tournaments.next_id;
+    tournaments.next_id += 1;
+    tournaments.entries.insert(id, Entry { tournament, admin_token : admin_token.clone(), rooms : Vec::new() });
+
+    Ok(Json(TournamentCreated { id, admin_token }))
+}
+
+/// GET /tournaments/:id , live bracket and standings
+pub async fn get_tournament(State(state) : State<AppState>, Path(id) : Path<TournamentId>) -> Result<Json<Bracket>, ApiError> {
+    let tournaments = state.tournaments.lock().await;
+    let entry = tournaments.entries.get(&id).ok_or((StatusCode::NOT_FOUND, "no such tournament".into()))?;
+    Ok(Json(bracket(id, entry)))
+}
+
+/// POST /tournaments/:id/register
+pub async fn register(State(state) : State<AppState>, Path(id) : Path<TournamentId>, Json(req) : Json<Register>) -> Result<StatusCode, ApiError> {
+    let mut tournaments = state.tournaments.lock().await;
+    let entry = tournaments.entries.get_mut(&id).ok_or((StatusCode::NOT_FOUND, "no such tournament".into()))?;
+    entry.tournament.register(&req.name).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /tournaments/:id/start , seats the first stage
+pub async fn start_tournament(State(state) : State<AppState>, Path(id) : Path<TournamentId>, Json(req) : Json<StartTournament>) -> Result<Json<Bracket>, ApiError> {
+    let mut tournaments = state.tournaments.lock().await;
+    let entry = tournaments.entries.get_mut(&id).ok_or((StatusCode::NOT_FOUND, "no such tournament".into()))?;
+    if entry.admin_token != req.admin_token {
+        return Err((StatusCode::FORBIDDEN, "wrong admin token".into()));
+    }
+
+    entry.tournament.start().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    open_tables(&state, id, entry).await;
+    Ok(Json(bracket(id, entry)))
+}
+
+/// one reserved room per table of the current stage , each watched for its result
+async fn open_tables(state : &AppState, id : TournamentId, entry : &mut Entry) {
+    let stage = entry.tournament.stage;
+    let mut rooms = state.rooms.write().await;
+
+    entry.rooms.clear();
+    for (idx, table) in entry.tournament.tables.iter().enumerate() {
+        let settings = RoomSettings { max_players : table.players.len(), private : true, ..rooms.default_settings() };
+        let room = rooms.create_reserved(settings, table.players.clone());
+        entry.rooms.push((room.id, room.code.clone()));
+        tokio::spawn(watch_table(state.clone(), id, stage, idx, room));
+    }
+}
+
+/// waits for the first finished game at a table and reports it to the tournament
+/// boxed because finishing a stage opens the next tables , which spawns this again
+/// only the receiver is kept , holding the room's sender would keep the channel open after the room is gone
+fn watch_table(state : AppState, id : TournamentId, stage : Stage, table : usize, room : Room) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+    let mut rx = room.tx.subscribe();
+    let game = room.game.clone();
+    let reserved = room.reserved.clone().unwrap_or_default();
+    drop(room);
+    Box::pin(async move {
+
+        let no_show = tokio::time::sleep(NO_SHOW_GRACE);
+        tokio::pin!(no_show);
+        let mut started = false;
+
+        let order = loop {
+            tokio::select! {
+                ev = rx.recv() => match ev {
+                    // names come with the summary , whoever left since then is still in it
+                    Ok(Event::GameOver { summary }) => break summary.standings(),
+                    Ok(Event::StartGame) => started = true,
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return,
+                },
+                () = &mut no_show, if !started => {
+                    started = true;
+                    if let Some(order) = forfeit(&game.read().await.get_names(), &reserved) {
+                        break order;
+                    }
+                }
+            }
+        };
+
+        let mut tournaments = state.tournaments.lock().await;
+        let Some(entry) = tournaments.entries.get_mut(&id) else {
+            return;
+        };
+        // results from an older stage no longer count
+        if entry.tournament.stage != stage {
+            return;
+        }
+        if entry.tournament.record_result(table, order) {
+            open_tables(&state, id, entry).await;
+        }
+    })
+}
+
+/// the result of a table that never started , None when everyone showed up.
+/// those seated go first in seat order , whoever didn't come forfeits and goes last
+fn forfeit(seated : &[String], reserved : &[String]) -> Option<Vec<String>> {
+    let (mut order, missing) : (Vec<String>, Vec<String>) = reserved.iter().cloned().partition(|name| seated.contains(name));
+    if missing.is_empty() {
+        return None;
+    }
+    order.sort_by_key(|name| seated.iter().position(|s| s == name));
+    order.extend(missing);
+    Some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names : &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn no_shows_forfeit_behind_those_seated() {
+        let reserved = names(&["ana", "bo", "cy"]);
+        assert_eq!(forfeit(&names(&["cy", "ana"]), &reserved), Some(names(&["cy", "ana", "bo"])));
+        assert_eq!(forfeit(&[], &reserved), Some(reserved.clone()));
+        assert_eq!(forfeit(&names(&["bo", "cy", "ana"]), &reserved), None);
+    }
+}
@@ -1,46 +1,115 @@
 use std::sync::Arc;
-use tokio::sync::{RwLock, broadcast, mpsc};
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock, mpsc};
  // {todo!("event is empty then send cant be added or early check if players are full , dont add")}
  // if person exit and game is in playing phase , broadcast abort game , reset game 
 use axum::{
     extract::{
-        State, ws::{Message, WebSocket, WebSocketUpgrade}
+        Query, State, ws::{Message, WebSocket, WebSocketUpgrade}
     },
-    response::IntoResponse,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
+use serde::Deserialize;
 
 use futures_util::{StreamExt, SinkExt};
 
-use crate::{game::{actions::Action, event::{Event, PrivateMsg}}, network::Messages::OutgoingMsg};
+use crate::{game::{actions::Action, event::{Event, PrivateMsg}}, network::messages::OutgoingMsg};
 use crate::game::logic::Game;
 use crate::game::logic::GamePhase;
+use crate::network::rooms::{DEFAULT_ROOM, Room, RoomId, Rooms};
+use crate::network::matchmaking::Matchmaker;
+use crate::network::tournament_handler::Tournaments;
+use crate::network::duplicate_handler::DuplicateSets;
+/// how long a closing socket may take to send what is still queued
+const FLUSH_TIMEOUT : Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct AppState {
-    pub game: Arc<RwLock<Game>>,
-    pub tx: broadcast::Sender<Event>,
+    pub rooms: Arc<RwLock<Rooms>>,
+    pub matchmaker: Arc<Mutex<Matchmaker>>,
+    pub tournaments: Arc<Mutex<Tournaments>>,
+    pub duplicates: Arc<Mutex<DuplicateSets>>,
+}
+
+/// query of `/ws` , no room or code means the default table
+#[derive(Deserialize)]
+pub struct JoinParams {
+    room : Option<RoomId>,
+    code : Option<String>,
+    password : Option<String>,
+    name : Option<String>,
 }
 
+pub async fn ws_handler(ws : WebSocketUpgrade , State(state) : State<AppState> , Query(params) : Query<JoinParams>) -> Response {
+    let room = {
+        let rooms = state.rooms.read().await;
+        match (&params.code, params.room) {
+            (Some(code), _) => rooms.find_by_code(code),
+            (None, Some(id)) => rooms.get(id),
+            (None, None) => rooms.get(DEFAULT_ROOM),
+        }
+    };
+
+    let Some(room) = room else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if let Err(status) = admit(&room, &params).await {
+        return status.into_response();
+    }
 
-pub async fn ws_handler(ws : WebSocketUpgrade , State(state) : State<AppState>) -> impl IntoResponse{ //why impl here ??
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, room, params.name))
+}
+
+/// whether `params` may open a socket at `room`
+async fn admit(room : &Room, params : &JoinParams) -> Result<(), StatusCode> {
+    // private rooms only open with their invite code , pretend they don't exist otherwise
+    if params.code.is_none() && room.game.read().await.get_settings().private {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    if !room.check_password(params.password.as_deref()) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // reserved seats need a name from the list , whether it is still free is checked when taking the seat
+    if let Some(reserved) = &room.reserved
+        && !params.name.as_deref().map(str::trim).is_some_and(|n| reserved.iter().any(|r| r == n)) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(mut socket: WebSocket, state: AppState, room: Room, name: Option<String>) {
+    let name = name.map(|n| n.trim().to_string());
 
     // join
-    let (player_id, join_event) = {
-        let mut game = state.game.write().await;
+    let (player_id, join_event, room_msg) = {
+        let mut game = room.game.write().await;
+        // checked under the lock the seat is taken with , two sockets can't both sit down with one reserved name
+        if room.reserved.is_some() && name.as_ref().is_some_and(|name| game.get_names().contains(name)) {
+            drop(game);
+            let json = serde_json::to_string(&Event::Error { message: "that name is already seated".into() }).unwrap();
+            let _ = socket.send(Message::Text(json)).await;
+            return;
+        }
         match game.add_player() {
-            Some(Event::PlayerAdded { p_id }) => (p_id, Some(Event::PlayerAdded { p_id })),
+            Some(Event::PlayerAdded { p_id }) => {
+                if let Some(name) = &name {
+                    game.set_name(p_id, name);
+                }
+                let room_msg = PrivateMsg::Room { room_id: room.id, code: room.code.clone(), host: game.get_host(), settings: game.get_settings().clone() };
+                (p_id, Some(Event::PlayerAdded { p_id }), room_msg)
+            }
             _ => return,
         }
     };
 
     if let Some(ev) = join_event {
-        let _ = state.tx.send(ev);
+        let _ = room.tx.send(ev);
     }
 
-    let mut rx = state.tx.subscribe();
+    let mut rx = room.tx.subscribe();
     let (sender, mut receiver) = socket.split();
 
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<OutgoingMsg>();
@@ -50,8 +119,9 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         PrivateMsg::Id { p_id: player_id }
             )
     );
+    let _ = out_tx.send(OutgoingMsg::Private(room_msg));
     // ----- WRITING TASK -----
-    let writer_task = tokio::spawn(async move {
+    let mut writer_task = tokio::spawn(async move {
         let mut sender = sender;
 
         while let Some(msg) = out_rx.recv().await {
@@ -67,19 +137,23 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     });
 
     // ---- BROADCAST LISTENER ------
-    let game_for_broadcast = state.game.clone();
+    let game_for_broadcast = room.game.clone();
     let my_id = player_id;
     let public_tx = out_tx.clone();
 
-    let broadcast_task = tokio::spawn(async move {
-        let mut hand_sent = false;
-
+    let mut broadcast_task = tokio::spawn(async move {
         while let Ok(ev) = rx.recv().await {
             // forward public event
             let _ = public_tx.send(OutgoingMsg::Public(ev.clone()));
 
-            // send hand ONCE when StartGame is observed
-            if matches!(ev, Event::StartGame) && !hand_sent {
+            // host removed us , seat is already gone
+            if matches!(ev, Event::PlayerKicked { p_id } if p_id == my_id) {
+                return true;
+            }
+
+            // send hand every time a game (or rematch) starts or the hands change
+            let my_draw = matches!(ev, Event::CardDrawn { p_id } if p_id == my_id);
+            if my_draw || matches!(ev, Event::StartGame | Event::HandsDealt) {
                 let hand = {
                     let game = game_for_broadcast.read().await;
                     game.get_hand(my_id)
@@ -91,16 +165,19 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                             PrivateMsg::Hand { cards }
                         )
                     );
-                    hand_sent = true; //sends cards exactly once
                 }
             }
         }
+        false
     });
 
     // ---- RECEIVE TASK ----
-    let recv_task = {
-        let game = state.game.clone();
-        let tx = state.tx.clone();
+    let mut recv_task = {
+        let game = room.game.clone();
+        let tx = room.tx.clone();
+        let engines = room.engines.clone();
+        let private_tx = out_tx.clone();
+        let room_id = room.id;
 
         tokio::spawn(async move {
             while let Some(Ok(Message::Text(text))) = receiver.next().await {
@@ -108,19 +185,40 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                     continue;
                 };
 
-                let mut started_game = false;
+                // a socket can only act for its own seat
+                if action.player_id().is_some_and(|p_id| p_id != my_id) {
+                    continue;
+                }
+                // only engines on the server's list can be seated
+                if let Action::AddBot { profile, .. } = &action
+                    && let Some(name) = &profile.engine
+                    && engines.get(name).is_none() {
+                    let _ = tx.send(Event::Error { message: format!("no engine called {name}") });
+                    continue;
+                }
+                // `/ws` without a code lands on the default table , it can't be hidden
+                if room_id == DEFAULT_ROOM
+                    && let Action::UpdateSettings { settings, .. } = &action
+                    && settings.private {
+                    let _ = tx.send(Event::Error { message: "the default room can't be private".into() });
+                    continue;
+                }
+                // hints are only for the one asking
+                if let Action::Hint { .. } = action {
+                    let msg = match game.read().await.hints(my_id) {
+                        Ok(hints) => OutgoingMsg::Private(PrivateMsg::Hints { hints }),
+                        Err(message) => OutgoingMsg::Public(Event::Error { message }),
+                    };
+                    let _ = private_tx.send(msg);
+                    continue;
+                }
+                let leaving = matches!(action, Action::LeaveTable { .. });
 
                 let events = {
                     let mut game = game.write().await;
 
-                    let events = game.apply_action(action);
-
-                    // deal cards ONCE, globally
-                    if game.get_phase() == GamePhase::Playing && !game.cards_dealt() {
-                        game.start_game();
-                        started_game = true;
-                    }
-
+                    let mut events = game.apply_action(action);
+                    events.extend(deal_if_started(&mut game));
                     events
                 };
 
@@ -129,36 +227,112 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                     let _ = tx.send(ev);
                 }
 
-                // broadcast turn info ONCE
-                if started_game {
-                    let (turn,seating) = {
-                        let game = game.read().await;
-                        (game.get_turn() , game.get_seats())
-                    };
-                    let _ = tx.send(Event::SeatOrder { seats: seating });
-                    let _ = tx.send(Event::NextTurn { player_id: turn });
+                if leaving {
+                    return true;
                 }
             }
+            false
         })
     };
 
     // wait....
-    tokio::select! {
-        _ = writer_task => {},
-        _ = broadcast_task => {},
-        _ = recv_task => {},
+    let (left_table, writer_done) = tokio::select! {
+        _ = &mut writer_task => (false, true),
+        kicked = &mut broadcast_task => (kicked.unwrap_or(false), false),
+        left = &mut recv_task => (left.unwrap_or(false), false),
+    };
+
+    // whichever finished first , the others must not outlive the connection
+    broadcast_task.abort();
+    recv_task.abort();
+
+    // the writer stops once every sender is gone , what is queued still goes out , a kick has to reach the one kicked
+    drop(out_tx);
+    if !writer_done && tokio::time::timeout(FLUSH_TIMEOUT, &mut writer_task).await.is_err() {
+        writer_task.abort();
     }
 
-    // ---- LEAVE ----
-    let outcome = {
-        let mut g = state.game.write().await;
-        g.remove_player(player_id)
-    };
+    // seat was already given up through LeaveTable or a kick , the id may belong to someone else by now
+    if !left_table {
+        // ---- LEAVE ----
+        let outcome = {
+            let mut g = room.game.write().await;
+            let mut events = g.remove_player(player_id);
+            // the last missing rematch vote may have been the one that left
+            events.extend(deal_if_started(&mut g));
+            events
+        };
+
+        for ev in outcome {
+            let _ = room.tx.send(ev);
+        }
+    }
+
+    // nobody left at the table , drop it , unless a tournament or duplicate set still needs it
+    if room.managed {
+        return;
+    }
+    let mut rooms = state.rooms.write().await;
+    if room.game.read().await.is_empty() {
+        rooms.remove(room.id);
+    }
+}
+
 
-    if let Some(ev) = outcome {
-        let _ = state.tx.send(ev);
+   
+/// deals cards once the game has moved to Playing , returns the turn info to broadcast
+fn deal_if_started(game : &mut Game) -> Vec<Event> {
+    if game.get_phase() != GamePhase::Playing || game.cards_dealt() {
+        return vec![];
     }
+
+    game.start_game();
+    let mut events = vec![Event::SeatOrder { seats: game.get_seats() , names: game.get_names() , bots: game.get_bots() }];
+    if let Some(teams) = game.get_teams() {
+        events.push(Event::Teams { teams });
+    }
+    events.extend(game.opening_events());
+    events
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::engine::Engines;
+    use crate::game::rules::HouseRules;
+    use crate::game::settings::RoomSettings;
+
+    fn params(code : Option<&str>, password : Option<&str>, name : Option<&str>) -> JoinParams {
+        JoinParams { room : None, code : code.map(Into::into), password : password.map(Into::into), name : name.map(Into::into) }
+    }
+
+    fn rooms() -> Rooms {
+        Rooms::new(Arc::new(RwLock::new(Game::new())), HouseRules::default(), Engines::default())
+    }
+
+    #[tokio::test]
+    async fn private_rooms_need_their_code() {
+        let room = rooms().create(RoomSettings { private : true, ..RoomSettings::default() }, None);
 
-   
\ No newline at end of file
+        assert_eq!(admit(&room, &params(None, None, None)).await, Err(StatusCode::NOT_FOUND));
+        assert_eq!(admit(&room, &params(Some(&room.code), None, None)).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn passwords_are_checked() {
+        let room = rooms().create(RoomSettings::default(), Some("hunter2".into()));
+
+        assert_eq!(admit(&room, &params(None, None, None)).await, Err(StatusCode::FORBIDDEN));
+        assert_eq!(admit(&room, &params(Some(&room.code), Some("nope"), None)).await, Err(StatusCode::FORBIDDEN));
+        assert_eq!(admit(&room, &params(None, Some("hunter2"), None)).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn reserved_rooms_need_a_listed_name() {
+        let room = rooms().create_reserved(RoomSettings::default(), vec!["ana".into(), "bo".into()]);
+
+        assert_eq!(admit(&room, &params(None, None, None)).await, Err(StatusCode::FORBIDDEN));
+        assert_eq!(admit(&room, &params(None, None, Some("cy"))).await, Err(StatusCode::FORBIDDEN));
+        assert_eq!(admit(&room, &params(None, None, Some(" bo "))).await, Ok(()));
+    }
+}
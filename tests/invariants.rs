@@ -0,0 +1,232 @@
+// property tests for the classic game , random tables play random legal and illegal moves
+// and every `apply_action` is followed by a check of what must always hold
+//
+//   every one of the 52 cards is in exactly one place , a hand , the trick on the table or the discards ,
+//   until the game is over and people start leaving
+//   while the game is on the turn belongs to a seated player who is still in and holds cards
+//   a move the game turns down changes nothing
+//   playing only legal cards the game always ends , with at most one player left holding cards
+//
+// proptest shrinks a failing case to the fewest , simplest moves and prints it ,
+// the failing seed is kept in `tests/invariants.proptest-regressions` and tried first from then on
+
+use std::collections::HashMap;
+use proptest::prelude::*;
+use card_game::bot::MAX_PLAYS;
+use card_game::game::actions::Action;
+use card_game::game::card::{Card, standard_deck};
+use card_game::game::event::Event;
+use card_game::game::logic::{Game, GamePhase};
+use card_game::game::players::PlayerId;
+use card_game::game::rng;
+use card_game::game::rules::{FoulTo, HouseRules};
+use card_game::game::settings::RoomSettings;
+
+/// something a player at the table does
+#[derive(Debug, Clone)]
+enum Move {
+    /// the player on turn plays one of their legal cards , picked by index
+    Legal(usize),
+    /// any seat plays any card , mostly turned down
+    Play(u8, Card),
+    Ready(u8),
+    Rematch(u8),
+    Leave(u8),
+}
+
+impl Move {
+    fn action(&self, game : &Game) -> Option<Action> {
+        let action = match *self {
+            Move::Legal(pick) => {
+                let turn = game.get_turn();
+                let legal = game.legal_cards(turn);
+                let card = *legal.get(pick % legal.len().max(1))?;
+                Action::CardPlayedByPlayer { player_id : turn, card, suit : None }
+            }
+            Move::Play(seat, card) => Action::CardPlayedByPlayer { player_id : PlayerId(seat.into()), card, suit : None },
+            Move::Ready(seat) => Action::Ready { player_id : PlayerId(seat.into()), name : None },
+            Move::Rematch(seat) => Action::Rematch { player_id : PlayerId(seat.into()) },
+            Move::Leave(seat) => Action::LeaveTable { player_id : PlayerId(seat.into()) },
+        };
+        Some(action)
+    }
+}
+
+fn card() -> impl Strategy<Value = Card> {
+    let deck = standard_deck();
+    (0..deck.len()).prop_map(move |i| deck[i])
+}
+
+fn moves() -> impl Strategy<Value = Vec<Move>> {
+    let one = prop_oneof![
+        12 => (0..13usize).prop_map(Move::Legal),
+        6 => (0..4u8, card()).prop_map(|(seat, card)| Move::Play(seat, card)),
+        1 => (0..4u8).prop_map(Move::Ready),
+        1 => (0..4u8).prop_map(Move::Rematch),
+        1 => (0..4u8).prop_map(Move::Leave),
+    ];
+    prop::collection::vec(one, 0..300)
+}
+
+fn rules() -> impl Strategy<Value = HouseRules> {
+    (any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()).prop_map(|(ace_of_spades_opens, to_leader, empty_hand_rescue, ace_low)| HouseRules {
+        ace_of_spades_opens,
+        foul_to : if to_leader { FoulTo::Leader } else { FoulTo::Highest },
+        empty_hand_rescue,
+        ace_low,
+    })
+}
+
+/// a table of 2 to 4 , four may play in teams
+fn settings() -> impl Strategy<Value = RoomSettings> {
+    (2..=4usize, any::<bool>(), rules()).prop_map(|(max_players, teams, rules)| RoomSettings {
+        max_players,
+        team_mode : teams && max_players == 4,
+        rules,
+        ..RoomSettings::default()
+    })
+}
+
+/// a seeded table with everyone ready and the cards dealt
+fn table(settings : RoomSettings, seed : u64) -> Game {
+    rng::seed(Some(seed));
+    let players = settings.max_players;
+    let mut game = Game::with_settings(settings);
+    for seat in 0..players {
+        game.add_player();
+        apply(&mut game, Action::Ready { player_id : PlayerId(seat as u32), name : None });
+    }
+    game
+}
+
+/// applies an action and deals when it started a game , like the server does
+fn apply(game : &mut Game, action : Action) -> Vec<Event> {
+    let events = game.apply_action(action);
+    if game.get_phase() == GamePhase::Playing && !game.cards_dealt() {
+        game.start_game();
+    }
+    events
+}
+
+/// everything a turned down move must leave alone
+#[derive(Debug, PartialEq)]
+struct Snapshot {
+    phase : GamePhase,
+    turn : PlayerId,
+    hands : Vec<(PlayerId, Vec<Card>, bool)>,
+    trick : Vec<(Card, PlayerId)>,
+    gone : Vec<Card>,
+}
+
+fn snapshot(game : &Game) -> Snapshot {
+    Snapshot {
+        phase : game.get_phase(),
+        turn : game.get_turn(),
+        hands : game.get_seats().into_iter().map(|p_id| (p_id, game.get_hand(p_id).unwrap_or_default(), game.is_finished(p_id))).collect(),
+        trick : game.get_trick().to_vec(),
+        gone : standard_deck().into_iter().filter(|c| game.get_knowledge().is_gone(c)).collect(),
+    }
+}
+
+/// the game said no , `AbortGame` alone is the answer to a card that isn't in the hand
+fn turned_down(events : &[Event]) -> bool {
+    matches!(events, [Event::AbortGame])
+        || events.iter().all(|ev| matches!(ev, Event::InvalidCard { .. } | Event::InvalidPlayer | Event::Error { .. }))
+}
+
+/// `events` are what the last action gave , the cards are counted while playing and once more as the game ends ,
+/// after that people leaving the post game lobby take their hands with them
+fn check(game : &Game, events : &[Event]) -> Result<(), TestCaseError> {
+    let game_over = events.iter().any(|ev| matches!(ev, Event::GameOver { .. }));
+    if !game.cards_dealt() || !(game.get_phase() == GamePhase::Playing || game_over) {
+        return Ok(());
+    }
+
+    let mut places : HashMap<Card, Vec<String>> = HashMap::new();
+    for p_id in game.get_seats() {
+        for card in game.get_hand(p_id).unwrap_or_default() {
+            places.entry(card).or_default().push(format!("P{}'s hand", p_id.0));
+        }
+    }
+    for (card, _) in game.get_trick() {
+        places.entry(*card).or_default().push("the trick".into());
+    }
+    for card in standard_deck() {
+        if game.get_knowledge().is_gone(&card) {
+            places.entry(card).or_default().push("the discards".into());
+        }
+    }
+    for card in standard_deck() {
+        let at = places.remove(&card).unwrap_or_default();
+        prop_assert!(at.len() == 1, "{card} is in {} places : {}", at.len(), at.join(" , "));
+    }
+    prop_assert!(places.is_empty(), "cards that aren't in the deck : {:?}", places.keys().collect::<Vec<_>>());
+
+    if game.get_phase() == GamePhase::Playing {
+        let turn = game.get_turn();
+        prop_assert!(game.get_seats().contains(&turn), "P{} is on turn but not seated", turn.0);
+        prop_assert!(!game.is_finished(turn), "P{} is on turn but already out", turn.0);
+        prop_assert!(game.hand_len(turn) > 0, "P{} is on turn with an empty hand", turn.0);
+        prop_assert!(!game.legal_cards(turn).is_empty(), "P{} is on turn with nothing to play", turn.0);
+    }
+    Ok(())
+}
+
+/// plays `moves` checking after every action , then plays legal cards until the game ends
+fn play_out(settings : RoomSettings, seed : u64, moves : &[Move], picks : &[usize]) -> Result<(), TestCaseError> {
+    let teams = settings.team_mode;
+    let mut game = table(settings, seed);
+    check(&game, &[])?;
+
+    for (n, mv) in moves.iter().enumerate() {
+        let Some(action) = mv.action(&game) else {
+            continue;
+        };
+        let before = snapshot(&game);
+        let events = apply(&mut game, action);
+        check(&game, &events).map_err(|e| TestCaseError::fail(format!("after move {n} {mv:?} : {e}")))?;
+        if turned_down(&events) {
+            prop_assert_eq!(&before, &snapshot(&game), "move {} {:?} was turned down with {} but changed the game", n, mv, serde_json::to_string(&events).unwrap_or_default());
+        }
+    }
+
+    if game.get_phase() != GamePhase::Playing {
+        return Ok(());
+    }
+    let mut game_over = false;
+    for n in 0..MAX_PLAYS as usize {
+        if game.get_phase() != GamePhase::Playing {
+            break;
+        }
+        let pick = picks.get(n % picks.len().max(1)).copied().unwrap_or_default();
+        let Some(action) = Move::Legal(pick).action(&game) else {
+            return Err(TestCaseError::fail(format!("P{} has no legal card", game.get_turn().0)));
+        };
+        let events = apply(&mut game, action);
+        game_over |= events.iter().any(|ev| matches!(ev, Event::GameOver { .. }));
+        check(&game, &events).map_err(|e| TestCaseError::fail(format!("after legal play {n} : {e}")))?;
+    }
+
+    prop_assert_eq!(game.get_phase(), GamePhase::Ended, "still playing after {} legal plays", MAX_PLAYS);
+    prop_assert!(game_over, "the game ended without GameOver");
+    // without the rescue the last players can go out on the same trick , then nobody is left
+    let left = game.get_seats().into_iter().filter(|&p_id| !game.is_finished(p_id)).count();
+    if !teams {
+        prop_assert!(left <= 1, "{} players still hold cards at the end", left);
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn random_moves_keep_invariants(settings in settings(), seed in any::<u64>(), moves in moves(), picks in prop::collection::vec(0..13usize, 1..8)) {
+        play_out(settings, seed, &moves, &picks)?;
+    }
+
+    #[test]
+    fn legal_play_always_ends(settings in settings(), seed in any::<u64>(), picks in prop::collection::vec(0..13usize, 1..64)) {
+        play_out(settings, seed, &[], &picks)?;
+    }
+}
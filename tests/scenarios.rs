@@ -0,0 +1,199 @@
+// runs every `tests/scenarios/*.scn` file against the classic game
+//
+//   # comment
+//   rule foul_to = "Leader"      house rule , any key of `HouseRules` in toml syntax
+//   seed 7                       seeds the random rescue card , 0 when left out
+//   lead P1                      who starts when nobody holds A♠ , P0 when left out
+//   P0: AS KH 7C                 hands , one line per seat in seat order
+//   P1: 2S 3H
+//
+//   play P0 AS                   a card played , or `leave P1` for LeaveTable
+//     CardPlayed P0 AS           the events it must give , indented , all of them in order
+//     NextTurn P1                `...` as the last one skips whatever follows
+//   hand P0: KH 7C               checks a hand , order doesn't matter
+//   turn P1                      checks whose turn it is
+//   finished P2                  checks someone is out
+//
+// cards are written like `Card` reads them , `10H` or `TH` , events like `describe` prints them
+
+use std::fmt::Write as _;
+use std::path::Path;
+use card_game::game::actions::Action;
+use card_game::game::card::Card;
+use card_game::game::event::Event;
+use card_game::game::logic::Game;
+use card_game::game::players::PlayerId;
+use card_game::game::rng;
+use card_game::game::rules::HouseRules;
+use card_game::game::settings::RoomSettings;
+
+fn player(token : &str) -> Result<PlayerId, String> {
+    token.strip_prefix('P').and_then(|n| n.parse().ok()).map(PlayerId).ok_or_else(|| format!("not a player: {token}"))
+}
+
+fn cards(text : &str) -> Result<Vec<Card>, String> {
+    text.split_whitespace().map(str::parse).collect()
+}
+
+fn list(cards : &[Card]) -> String {
+    cards.iter().map(Card::to_string).collect::<Vec<_>>().join(" ")
+}
+
+/// the one line form events are written in
+fn describe(ev : &Event) -> String {
+    match ev {
+        Event::CardPlayed { card, p_id } => format!("CardPlayed P{} {card}", p_id.0),
+        Event::NextTurn { player_id } => format!("NextTurn P{}", player_id.0),
+        Event::FoulGiven { from, to, cards } => format!("FoulGiven P{} -> P{} {}", from.0, to.0, list(cards)),
+        Event::DiscardPile => "DiscardPile".into(),
+        Event::PlayerWon { player_id } => format!("PlayerWon P{}", player_id.0),
+        Event::SpecialEvent { p_id, card, from } => format!("SpecialEvent P{} {card} from P{}", p_id.0, from.0),
+        Event::EndGame { p_id } => format!("EndGame P{}", p_id.0),
+        Event::GameOver { .. } => "GameOver".into(),
+        Event::InvalidCard { p_id } => format!("InvalidCard P{}", p_id.0),
+        Event::InvalidPlayer => "InvalidPlayer".into(),
+        Event::AbortGame => "AbortGame".into(),
+        Event::PlayerLeft { p_id } => format!("PlayerLeft P{}", p_id.0),
+        Event::HostChanged { p_id } => format!("HostChanged P{}", p_id.0),
+        Event::Error { message } => format!("Error {message}"),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
+
+/// cards in an expected line are read and written back so `10h` matches `TH`
+fn normalize(line : &str) -> String {
+    line.split_whitespace()
+        .map(|token| token.parse::<Card>().map_or_else(|_| token.to_string(), |c| c.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+struct Setup {
+    rules : String,
+    seed : u64,
+    lead : usize,
+    hands : Vec<Vec<Card>>,
+}
+
+/// runs one file , the error names the line that went wrong
+fn run(text : &str) -> Result<(), String> {
+    let mut lines = text.lines().enumerate().map(|(n, l)| (n + 1, l)).filter(|(_, l)| !l.trim().is_empty() && !l.trim().starts_with('#')).peekable();
+
+    let mut setup = Setup { rules : String::new(), seed : 0, lead : 0, hands : Vec::new() };
+    while let Some(&(n, line)) = lines.peek() {
+        let at = |e : String| format!("line {n}: {e}");
+        let line = line.trim();
+        if let Some(rule) = line.strip_prefix("rule ") {
+            writeln!(setup.rules, "{rule}").expect("writing to a string");
+        } else if let Some(seed) = line.strip_prefix("seed ") {
+            setup.seed = seed.trim().parse().map_err(|_| at(format!("bad seed {seed}")))?;
+        } else if let Some(lead) = line.strip_prefix("lead ") {
+            setup.lead = player(lead.trim()).map_err(at)?.0 as usize;
+        } else if let Some((seat, hand)) = line.split_once(':').filter(|(seat, _)| seat.starts_with('P')) {
+            if player(seat.trim()).map_err(at)?.0 as usize != setup.hands.len() {
+                return Err(at(format!("hands go in seat order , expected P{}", setup.hands.len())));
+            }
+            setup.hands.push(cards(hand).map_err(at)?);
+        } else {
+            break;
+        }
+        lines.next();
+    }
+
+    let rules : HouseRules = toml::from_str(&setup.rules).map_err(|e| format!("bad rules: {e}"))?;
+    rng::seed(Some(setup.seed));
+    let mut game = Game::with_settings(RoomSettings { max_players : setup.hands.len(), rules, ..RoomSettings::default() });
+    for _ in 0..setup.hands.len() {
+        game.add_player();
+    }
+    game.set_preset_hands(setup.hands, setup.lead);
+    game.start_game();
+
+    while let Some((n, line)) = lines.next() {
+        let at = |e : String| format!("line {n}: {e}");
+        let words : Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["play", p, card] => {
+                let action = Action::CardPlayedByPlayer { player_id : player(p).map_err(at)?, card : card.parse().map_err(at)?, suit : None };
+                expect(&mut lines, game.apply_action(action)).map_err(at)?;
+            }
+            ["leave", p] => {
+                let action = Action::LeaveTable { player_id : player(p).map_err(at)? };
+                expect(&mut lines, game.apply_action(action)).map_err(at)?;
+            }
+            ["hand", ..] => {
+                let (seat, want) = line.trim().trim_start_matches("hand").split_once(':').ok_or_else(|| at("hand needs a `:`".into()))?;
+                let p_id = player(seat.trim()).map_err(at)?;
+                let mut want = cards(want).map_err(at)?;
+                let mut got = game.get_hand(p_id).unwrap_or_default();
+                want.sort_by_key(Card::to_string);
+                got.sort_by_key(Card::to_string);
+                if want != got {
+                    return Err(at(format!("P{} holds {} , not {}", p_id.0, list(&got), list(&want))));
+                }
+            }
+            ["turn", p] => {
+                let p_id = player(p).map_err(at)?;
+                if game.get_turn() != p_id {
+                    return Err(at(format!("it is P{}'s turn", game.get_turn().0)));
+                }
+            }
+            ["finished", p] => {
+                let p_id = player(p).map_err(at)?;
+                if !game.is_finished(p_id) {
+                    return Err(at(format!("P{} is still playing", p_id.0)));
+                }
+            }
+            _ => return Err(at(format!("don't know what to do with `{}`", line.trim()))),
+        }
+    }
+    Ok(())
+}
+
+/// compares the indented lines after an action with the events it gave
+fn expect<'a>(lines : &mut std::iter::Peekable<impl Iterator<Item = (usize, &'a str)>>, events : Vec<Event>) -> Result<(), String> {
+    let got : Vec<String> = events.iter().map(describe).collect();
+    let mut want = Vec::new();
+    while let Some(&(_, line)) = lines.peek() {
+        if !line.starts_with([' ', '\t']) {
+            break;
+        }
+        want.push(normalize(line));
+        lines.next();
+    }
+
+    let rest = want.last().is_some_and(|w| w == "...");
+    if rest {
+        want.pop();
+    }
+    let matches = if rest { got.starts_with(&want) } else { got == want };
+    if !matches {
+        return Err(format!("expected\n    {}\n  got\n    {}", want.join("\n    "), got.join("\n    ")));
+    }
+    Ok(())
+}
+
+#[test]
+fn scenarios() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    let mut files : Vec<_> = std::fs::read_dir(&dir).expect("tests/scenarios exists")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "scn"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no scenarios in {}", dir.display());
+
+    let mut failed = Vec::new();
+    for path in &files {
+        let name = path.file_name().expect("files have names").to_string_lossy().to_string();
+        let text = std::fs::read_to_string(path).expect("scenario is readable");
+        match run(&text) {
+            Ok(()) => println!("ok   {name}"),
+            Err(e) => {
+                println!("FAIL {name} , {e}");
+                failed.push(name);
+            }
+        }
+    }
+    assert!(failed.is_empty(), "failing scenarios: {}", failed.join(" , "));
+}